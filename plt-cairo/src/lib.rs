@@ -1,8 +1,10 @@
-use std::{error, f64, marker, path};
-#[cfg(any(feature = "svg", feature = "png"))]
-use std::{fs, io};
-#[cfg(feature = "svg")]
-use std::env;
+use std::{error, f64, fs, marker, path};
+
+/// The `cairo-rs` crate the backend is built on, for creating contexts for [`CairoCanvas::from_context`].
+pub use cairo;
+
+#[cfg(feature = "fixed-metrics")]
+mod metrics;
 
 /// Converts a Cairo error to a draw error.
 fn convert_err<E: error::Error + marker::Sync + marker::Send + 'static>(
@@ -11,17 +13,53 @@ fn convert_err<E: error::Error + marker::Sync + marker::Send + 'static>(
     draw::DrawError::BackendError(e.into())
 }
 
+/// Divides alpha out of a color channel premultiplied by it, rounding to the nearest value.
+fn unpremultiply(channel: u8, alpha: u8) -> u8 {
+    if alpha == 0 {
+        return 0;
+    }
+
+    let (channel, alpha) = (channel as u32, alpha as u32);
+    ((channel * 255 + alpha / 2) / alpha).min(255) as u8
+}
+
+/// Sets the source color of a context.
+fn set_source_color(context: &cairo::Context, color: draw::Color) {
+    for (name, value) in [("r", color.r), ("g", color.g), ("b", color.b), ("a", color.a)] {
+        debug_assert!(value.is_finite(), "color channel `{}` must be finite, found {}", name, value);
+        debug_assert!(
+            (0.0..=1.0).contains(&value),
+            "color channel `{}` must be from 0.0 to 1.0, found {}",
+            name, value,
+        );
+    }
+
+    context.set_source_rgba(color.r, color.g, color.b, color.a);
+}
+
+/// Adds a closed path through offsets from an origin.
+fn polygon(context: &cairo::Context, origin: CairoPoint, offsets: &[(f64, f64)]) {
+    for &(x, y) in offsets {
+        context.line_to(origin.x + x, origin.y + y);
+    }
+    context.close_path();
+}
+
 /// The Cairo backend for `plt`.
 #[derive(Debug)]
 pub struct CairoCanvas {
     size: draw::Size,
     context: cairo::Context,
     image_format: draw::ImageFormat,
+    /// Whether the canvas owns an SVG or PDF surface writing to memory.
     #[allow(dead_code)]
-    temp_file: Option<path::PathBuf>,
+    stream: bool,
 }
 impl CairoCanvas {
     /// Construct from existing context.
+    ///
+    /// Nothing is painted when constructed, so drawing composites over the existing content of the context,
+    /// and stays within any clip the context already has.
     pub fn from_context(
         context: &cairo::Context,
         size: draw::Size,
@@ -31,13 +69,30 @@ impl CairoCanvas {
             size,
             context: context.clone(),
             image_format,
-            temp_file: None,
+            stream: false,
         }
     }
+
+    /// Finishes writing the document of a vector surface, and takes it from the surface.
+    #[cfg(any(feature = "svg", feature = "pdf"))]
+    fn finish_stream(&mut self) -> Result<Vec<u8>, draw::DrawError> {
+        if !self.stream {
+            return Err(draw::DrawError::UnsupportedImageFormat(format!(
+                "{:?} surfaces from other contexts cannot be encoded to bytes", self.image_format,
+            )));
+        }
+
+        let stream = self.context.target()
+            .finish_output_stream()
+            .map_err(|e| convert_err(e.error))?;
+        self.stream = false;
+
+        Ok(*stream.downcast::<Vec<u8>>().unwrap())
+    }
 }
 impl draw::Canvas for CairoCanvas {
     fn new(desc: draw::CanvasDescriptor) -> Result<Self, draw::DrawError> {
-        let (context, temp_file) = match desc.image_format {
+        let (context, stream) = match desc.image_format {
             draw::ImageFormat::Bitmap => {
                 let surface = cairo::ImageSurface::create(
                     cairo::Format::ARgb32,
@@ -46,23 +101,19 @@ impl draw::Canvas for CairoCanvas {
                 )
                 .map_err(convert_err)?;
 
-                (cairo::Context::new(&surface).map_err(convert_err)?, None)
+                (cairo::Context::new(&surface).map_err(convert_err)?, false)
             },
             draw::ImageFormat::Svg => {
                 #[cfg(feature = "svg")]
                 {
-                    let mut temp_filename = env::temp_dir();
-                    temp_filename.push("plt_temp.svg");
-                    let temp_file = Some(temp_filename);
-
-                    let surface = cairo::SvgSurface::new(
+                    let surface = cairo::SvgSurface::for_stream(
                         desc.size.width.into(),
                         desc.size.height.into(),
-                        temp_file.as_ref(),
+                        Vec::<u8>::new(),
                     )
                     .map_err(|e| draw::DrawError::BackendError(e.into()))?;
 
-                    (cairo::Context::new(&surface).map_err(convert_err)?, temp_file)
+                    (cairo::Context::new(&surface).map_err(convert_err)?, true)
                 }
 
                 #[cfg(not(feature = "svg"))]
@@ -70,6 +121,24 @@ impl draw::Canvas for CairoCanvas {
                     "svg feature is not enabled".to_string()
                 ))
             },
+            draw::ImageFormat::Pdf => {
+                #[cfg(feature = "pdf")]
+                {
+                    let surface = cairo::PdfSurface::for_stream(
+                        desc.size.width.into(),
+                        desc.size.height.into(),
+                        Vec::<u8>::new(),
+                    )
+                    .map_err(|e| draw::DrawError::BackendError(e.into()))?;
+
+                    (cairo::Context::new(&surface).map_err(convert_err)?, true)
+                }
+
+                #[cfg(not(feature = "pdf"))]
+                return Err(draw::DrawError::UnsupportedImageFormat(
+                    "pdf feature is not enabled".to_string()
+                ))
+            },
             image_format => {
                 return Err(draw::DrawError::UnsupportedImageFormat(
                     format!("{:?} is not supported by the Cairo backend", image_format)
@@ -77,20 +146,27 @@ impl draw::Canvas for CairoCanvas {
             }
         };
 
-        context.set_source_rgba(
-            desc.face_color.r,
-            desc.face_color.g,
-            desc.face_color.b,
-            desc.face_color.a,
-        );
+        if !desc.antialias {
+            context.set_antialias(cairo::Antialias::None);
+
+            // text takes its anti-aliasing from font options, not the context
+            let mut font_options = cairo::FontOptions::new().map_err(convert_err)?;
+            font_options.set_antialias(cairo::Antialias::None);
+            context.set_font_options(&font_options);
+        }
 
-        context.paint().unwrap();
+        // replace the blank surface with the face color, so transparent face colors stay transparent
+        context.save().map_err(convert_err)?;
+        context.set_operator(cairo::Operator::Source);
+        set_source_color(&context, desc.face_color);
+        context.paint().map_err(convert_err)?;
+        context.restore().map_err(convert_err)?;
 
         Ok(Self {
             size: desc.size,
             context,
             image_format: desc.image_format,
-            temp_file,
+            stream,
         })
     }
 
@@ -132,6 +208,48 @@ impl draw::Canvas for CairoCanvas {
                 );
                 self.context.close_path();
             },
+            draw::Shape::TriangleUp { l } | draw::Shape::TriangleDown { l } => {
+                let half_width = l as f64 / 2.0;
+                let half_height = l as f64 * 3f64.sqrt() / 4.0;
+                // cairo y-values increase downward
+                let apex = if let draw::Shape::TriangleUp { .. } = desc.shape {
+                    -half_height
+                } else {
+                    half_height
+                };
+
+                polygon(&self.context, origin, &[
+                    (0.0, apex),
+                    (half_width, -apex),
+                    (-half_width, -apex),
+                ]);
+            },
+            draw::Shape::Diamond { l } => {
+                let half = l as f64 / 2.0;
+
+                polygon(&self.context, origin, &[
+                    (0.0, -half),
+                    (half, 0.0),
+                    (0.0, half),
+                    (-half, 0.0),
+                ]);
+            },
+            draw::Shape::Plus { l, thickness } | draw::Shape::Cross { l, thickness } => {
+                let (a, b) = (l as f64 / 2.0, thickness as f64 / 2.0);
+                let plus = [
+                    (-b, -a), (b, -a), (b, -b), (a, -b), (a, b), (b, b),
+                    (b, a), (-b, a), (-b, b), (-a, b), (-a, -b), (-b, -b),
+                ];
+
+                if let draw::Shape::Cross { .. } = desc.shape {
+                    // rotate by 45 degrees
+                    polygon(&self.context, origin, &plus.map(|(x, y)| {
+                        ((x - y) * f64::consts::FRAC_1_SQRT_2, (x + y) * f64::consts::FRAC_1_SQRT_2)
+                    }));
+                } else {
+                    polygon(&self.context, origin, &plus);
+                }
+            },
             shape => {
                 return Err(draw::DrawError::UnsupportedShape(
                     format!("{:?} is not supported by the Cairo backend", shape)
@@ -140,27 +258,15 @@ impl draw::Canvas for CairoCanvas {
         };
 
         // fill shape
-        self.context.set_source_rgba(
-            desc.fill_color.r,
-            desc.fill_color.g,
-            desc.fill_color.b,
-            desc.fill_color.a,
-        );
+        set_source_color(&self.context, desc.fill_color);
         self.context.fill_preserve().map_err(convert_err)?;
 
         // outline shape
         self.context.set_dash(desc.line_dashes, 0.0);
         self.context.set_line_width(desc.line_width as f64);
-        self.context.set_source_rgba(
-            desc.line_color.r,
-            desc.line_color.g,
-            desc.line_color.b,
-            desc.line_color.a,
-        );
+        set_source_color(&self.context, desc.line_color);
         self.context.stroke().map_err(convert_err)?;
 
-        self.reset_clip();
-
         self.context.restore().map_err(convert_err)?;
 
         Ok(())
@@ -176,12 +282,7 @@ impl draw::Canvas for CairoCanvas {
             self.clip_area(area);
         }
 
-        self.context.set_source_rgba(
-            desc.line_color.r,
-            desc.line_color.g,
-            desc.line_color.b,
-            desc.line_color.a,
-        );
+        set_source_color(&self.context, desc.line_color);
         self.context.set_line_width(desc.line_width as f64);
 
         self.context.set_dash(desc.dashes, 0.0);
@@ -193,8 +294,6 @@ impl draw::Canvas for CairoCanvas {
 
         self.context.stroke().map_err(convert_err)?;
 
-        self.reset_clip();
-
         self.context.restore().map_err(convert_err)?;
 
         Ok(())
@@ -207,12 +306,7 @@ impl draw::Canvas for CairoCanvas {
             self.clip_area(area);
         }
 
-        self.context.set_source_rgba(
-            desc.line_color.r,
-            desc.line_color.g,
-            desc.line_color.b,
-            desc.line_color.a,
-        );
+        set_source_color(&self.context, desc.line_color);
         self.context.set_line_width(desc.line_width as f64);
         self.context.set_line_join(cairo::LineJoin::Round);
 
@@ -228,8 +322,6 @@ impl draw::Canvas for CairoCanvas {
 
         self.context.stroke().map_err(convert_err)?;
 
-        self.reset_clip();
-
         self.context.restore().map_err(convert_err)?;
 
         Ok(())
@@ -242,12 +334,7 @@ impl draw::Canvas for CairoCanvas {
             self.clip_area(area);
         }
 
-        self.context.set_source_rgba(
-            desc.fill_color.r,
-            desc.fill_color.g,
-            desc.fill_color.b,
-            desc.fill_color.a,
-        );
+        set_source_color(&self.context, desc.fill_color);
 
         for point in desc.points {
             let point = CairoPoint::from_point(point, self.size);
@@ -259,8 +346,6 @@ impl draw::Canvas for CairoCanvas {
 
         self.context.fill().map_err(convert_err)?;
 
-        self.reset_clip();
-
         self.context.restore().map_err(convert_err)?;
 
         Ok(())
@@ -275,12 +360,7 @@ impl draw::Canvas for CairoCanvas {
             self.clip_area(area);
         }
 
-        self.context.set_source_rgba(
-            desc.color.r,
-            desc.color.g,
-            desc.color.b,
-            desc.color.a,
-        );
+        set_source_color(&self.context, desc.color);
 
         self.context.select_font_face(
             &font_to_cairo(desc.font.name),
@@ -301,22 +381,26 @@ impl draw::Canvas for CairoCanvas {
 
         self.context.stroke().map_err(convert_err)?;
 
-        self.reset_clip();
-
         self.context.restore().map_err(convert_err)?;
 
         Ok(())
     }
 
+    /// Measures text with fixed metrics, instead of the installed fonts.
+    #[cfg(feature = "fixed-metrics")]
+    fn text_size(&mut self, desc: draw::TextDescriptor) -> Result<draw::Size, draw::DrawError> {
+        let (width, height) = metrics::text_size(&desc.text, &desc.font.name, desc.font.size as f64);
+
+        Ok(draw::Size {
+            width: width.ceil() as u32,
+            height: height.ceil() as u32,
+        })
+    }
+    #[cfg(not(feature = "fixed-metrics"))]
     fn text_size(&mut self, desc: draw::TextDescriptor) -> Result<draw::Size, draw::DrawError> {
         self.context.save().map_err(convert_err)?;
 
-        self.context.set_source_rgba(
-            desc.color.r,
-            desc.color.g,
-            desc.color.b,
-            desc.color.a,
-        );
+        set_source_color(&self.context, desc.color);
 
         self.context.select_font_face(
             &font_to_cairo(desc.font.name),
@@ -341,30 +425,45 @@ impl draw::Canvas for CairoCanvas {
         &mut self,
         desc: draw::SaveFileDescriptor<P>,
     ) -> Result<(), draw::DrawError> {
+        #[cfg(any(feature = "svg", feature = "pdf"))]
+        if matches!(self.image_format, draw::ImageFormat::Svg | draw::ImageFormat::Pdf) && !self.stream {
+            // surfaces from other contexts write to their own output
+            self.context.target().finish();
+
+            return Ok(());
+        }
+
+        let bytes = self.save_bytes(draw::SaveBytesDescriptor {
+            format: desc.format,
+            dpi: desc.dpi,
+            lossless: desc.lossless,
+            quality: desc.quality,
+        })?;
+
+        fs::write(desc.filename, bytes)?;
+
+        Ok(())
+    }
+    #[cfg_attr(
+        not(any(feature = "png", feature = "bmp", feature = "tiff", feature = "webp", feature = "jpeg")),
+        allow(unused_mut, unused_variables),
+    )]
+    fn save_bytes(
+        &mut self,
+        desc: draw::SaveBytesDescriptor,
+    ) -> Result<Vec<u8>, draw::DrawError> {
+        let mut bytes = Vec::new();
+
         match self.image_format {
             draw::ImageFormat::Bitmap => {
                 match desc.format {
                     #[cfg(feature = "png")]
                     draw::FileFormat::Png => {
-                        // temporarily remove surface from context
-                        let mut surface = cairo::ImageSurface::try_from(
-                            self.context.target()
-                        )
-                        .unwrap();
-                        let blank_surface = cairo::ImageSurface::create(
-                            cairo::Format::ARgb32,
-                            0,
-                            0,
-                        )
-                        .map_err(convert_err)?;
-                        self.context = cairo::Context::new(&blank_surface).map_err(convert_err)?;
-
-                        let file = fs::File::create(desc.filename)?;
-                        let w = &mut io::BufWriter::new(file);
+                        let buffer = self.rgba_buffer()?;
 
                         // configure encoder
                         let mut encoder = png::Encoder::new(
-                            w,
+                            &mut bytes,
                             self.size.width,
                             self.size.height,
                         );
@@ -372,13 +471,6 @@ impl draw::Canvas for CairoCanvas {
                         encoder.set_depth(png::BitDepth::Eight);
                         let mut writer = encoder.write_header().map_err(convert_err)?;
 
-                        // extract buffer from cairo
-                        let buffer_raw = surface.data().map_err(convert_err)?;
-                        // fix color byte ordering
-                        let buffer = buffer_raw.chunks(4)
-                            .flat_map(|rgba| [rgba[2], rgba[1], rgba[0], rgba[3]])
-                            .collect::<Vec<_>>();
-
                         // set dpi
                         let ppu = (desc.dpi as f64 * (1000.0 / 25.4)) as u32;
                         let xppu = ppu.to_be_bytes();
@@ -395,12 +487,7 @@ impl draw::Canvas for CairoCanvas {
                         .map_err(convert_err)?;
 
                         writer.write_image_data(&buffer[..]).map_err(convert_err)?;
-
-                        drop(buffer_raw);
-                        drop(buffer);
-
-                        // return surface to self
-                        self.context = cairo::Context::new(&surface).map_err(convert_err)?;
+                        writer.finish().map_err(convert_err)?;
                     },
                     #[cfg(not(feature = "png"))]
                     draw::FileFormat::Png => {
@@ -408,6 +495,105 @@ impl draw::Canvas for CairoCanvas {
                             "png feature is not enabled".to_string()
                         ))
                     },
+                    #[cfg(feature = "bmp")]
+                    draw::FileFormat::Bmp => {
+                        let buffer = self.rgba_buffer()?;
+
+                        // fully opaque images are written without alpha, for compatibility with older readers
+                        let (data, color_type) = if buffer.chunks(4).all(|pixel| pixel[3] == u8::MAX) {
+                            let rgb = buffer.chunks(4).flat_map(|pixel| [pixel[0], pixel[1], pixel[2]]).collect();
+                            (rgb, image::ExtendedColorType::Rgb8)
+                        } else {
+                            (buffer, image::ExtendedColorType::Rgba8)
+                        };
+                        image::codecs::bmp::BmpEncoder::new(&mut bytes)
+                            .encode(&data, self.size.width, self.size.height, color_type)
+                            .map_err(convert_err)?;
+
+                        // the encoder leaves the resolution unset, so fill in the dots per meter
+                        let ppm = (desc.dpi as f64 * (1000.0 / 25.4)).round() as u32;
+                        bytes[38..42].copy_from_slice(&ppm.to_le_bytes());
+                        bytes[42..46].copy_from_slice(&ppm.to_le_bytes());
+                    },
+                    #[cfg(not(feature = "bmp"))]
+                    draw::FileFormat::Bmp => {
+                        return Err(draw::DrawError::UnsupportedFileFormat(
+                            "bmp feature is not enabled".to_string()
+                        ))
+                    },
+                    #[cfg(feature = "tiff")]
+                    draw::FileFormat::Tiff => {
+                        let buffer = self.rgba_buffer()?;
+
+                        // written with the tiff crate directly, since the image crate cannot set a resolution
+                        let mut encoder = tiff::encoder::TiffEncoder::new(std::io::Cursor::new(&mut bytes))
+                            .map_err(convert_err)?;
+                        let mut image = encoder
+                            .new_image::<tiff::encoder::colortype::RGB8>(self.size.width, self.size.height)
+                            .map_err(convert_err)?;
+                        // alpha is an extra sample, so readers know it is not premultiplied
+                        image.extra_samples(&[tiff::tags::ExtraSamples::UnassociatedAlpha]).map_err(convert_err)?;
+                        image.resolution(
+                            tiff::tags::ResolutionUnit::Inch,
+                            tiff::encoder::Rational { n: desc.dpi as u32, d: 1 },
+                        );
+                        image.write_data(&buffer[..]).map_err(convert_err)?;
+                    },
+                    #[cfg(not(feature = "tiff"))]
+                    draw::FileFormat::Tiff => {
+                        return Err(draw::DrawError::UnsupportedFileFormat(
+                            "tiff feature is not enabled".to_string()
+                        ))
+                    },
+                    #[cfg(feature = "webp")]
+                    draw::FileFormat::WebP => {
+                        let buffer = self.rgba_buffer()?;
+
+                        let encoder = webp::Encoder::from_rgba(&buffer[..], self.size.width, self.size.height);
+                        let image = encoder.encode_simple(desc.lossless, desc.quality.min(100) as f32)
+                            .map_err(|e| draw::DrawError::UnsupportedFileFormat(
+                                format!("could not encode webp image: {:?}", e)
+                            ))?;
+
+                        bytes.extend_from_slice(&image);
+                    },
+                    #[cfg(not(feature = "webp"))]
+                    draw::FileFormat::WebP => {
+                        return Err(draw::DrawError::UnsupportedFileFormat(
+                            "webp feature is not enabled".to_string()
+                        ))
+                    },
+                    #[cfg(feature = "jpeg")]
+                    draw::FileFormat::Jpeg => {
+                        let buffer = self.rgba_buffer()?;
+
+                        let (width, height) = match (u16::try_from(self.size.width), u16::try_from(self.size.height)) {
+                            (Ok(width), Ok(height)) => (width, height),
+                            _ => return Err(draw::DrawError::UnsupportedFileFormat(
+                                "jpeg images must be at most 65535 pixels in each dimension".to_string()
+                            )),
+                        };
+
+                        // JPEG images have no alpha channel, so blend transparent pixels onto white
+                        let rgb = buffer.chunks(4)
+                            .flat_map(|pixel| {
+                                let alpha = pixel[3] as f64 / 255.0;
+                                [0, 1, 2].map(|c| (pixel[c] as f64 * alpha + 255.0 * (1.0 - alpha)).round() as u8)
+                            })
+                            .collect::<Vec<_>>();
+
+                        let mut encoder = jpeg_encoder::Encoder::new(&mut bytes, desc.quality.clamp(1, 100));
+                        encoder.set_density(jpeg_encoder::Density::Inch { x: desc.dpi, y: desc.dpi });
+                        // full resolution color, since chroma subsampling blurs thin colored lines
+                        encoder.set_sampling_factor(jpeg_encoder::SamplingFactor::R_4_4_4);
+                        encoder.encode(&rgb, width, height, jpeg_encoder::ColorType::Rgb).map_err(convert_err)?;
+                    },
+                    #[cfg(not(feature = "jpeg"))]
+                    draw::FileFormat::Jpeg => {
+                        return Err(draw::DrawError::UnsupportedFileFormat(
+                            "jpeg feature is not enabled".to_string()
+                        ))
+                    },
                     file_format => {
                         return Err(draw::DrawError::UnsupportedFileFormat(format!(
                             "{:?} is not supported by the Cairo backend for bitmap images",
@@ -419,22 +605,7 @@ impl draw::Canvas for CairoCanvas {
             draw::ImageFormat::Svg => {
                 #[cfg(feature = "svg")]
                 match desc.format {
-                    draw::FileFormat::Svg => {
-                        // finish writing file
-                        let old_surface = cairo::SvgSurface::try_from(
-                            self.context.target()
-                        )
-                        .unwrap();
-                        old_surface.finish();
-
-                        if let Some(temp_file) = &self.temp_file {
-                            // copy temp file to new specified location
-                            fs::copy(temp_file, desc.filename.as_ref())?;
-
-                            // remove temp file
-                            fs::remove_file(temp_file)?;
-                        }
-                    },
+                    draw::FileFormat::Svg => return self.finish_stream(),
                     file_format => {
                         return Err(draw::DrawError::UnsupportedFileFormat(
                             format!("{:?} is not supported for svg images", file_format)
@@ -447,6 +618,22 @@ impl draw::Canvas for CairoCanvas {
                     "svg feature is not enabled".to_string()
                 ))
             },
+            draw::ImageFormat::Pdf => {
+                #[cfg(feature = "pdf")]
+                match desc.format {
+                    draw::FileFormat::Pdf => return self.finish_stream(),
+                    file_format => {
+                        return Err(draw::DrawError::UnsupportedFileFormat(
+                            format!("{:?} is not supported for pdf images", file_format)
+                        ))
+                    },
+                }
+
+                #[cfg(not(feature = "pdf"))]
+                return Err(draw::DrawError::UnsupportedFileFormat(
+                    "pdf feature is not enabled".to_string()
+                ))
+            },
             image_format => {
                 return Err(draw::DrawError::UnsupportedImageFormat(
                     format!("{:?} is not supported by the Cairo backend", image_format)
@@ -455,18 +642,68 @@ impl draw::Canvas for CairoCanvas {
         };
 
         #[allow(unreachable_code)]
-        Ok(())
+        Ok(bytes)
     }
     fn size(&self) -> Result<draw::Size, draw::DrawError> {
         Ok(self.size)
     }
+    fn bitmap(&mut self) -> Result<draw::Bitmap, draw::DrawError> {
+        match self.image_format {
+            draw::ImageFormat::Bitmap => Ok(draw::Bitmap {
+                size: self.size,
+                data: self.rgba_buffer()?,
+            }),
+            image_format => Err(draw::DrawError::UnsupportedImageFormat(format!(
+                "cannot get an in-memory bitmap from a {:?} canvas",
+                image_format,
+            ))),
+        }
+    }
 }
 impl CairoCanvas {
-    fn reset_clip(&mut self) {
-        self.context.reset_clip();
+    /// Extracts the bitmap image as RGBA bytes, ordered row by row from the top.
+    ///
+    /// Cairo stores colors premultiplied by alpha, which are divided back out,
+    /// as encoders and [`draw::Bitmap`] take straight alpha.
+    fn rgba_buffer(&mut self) -> Result<Vec<u8>, draw::DrawError> {
+        // temporarily remove surface from context
+        let mut surface = cairo::ImageSurface::try_from(
+            self.context.target()
+        )
+        .unwrap();
+        let blank_surface = cairo::ImageSurface::create(
+            cairo::Format::ARgb32,
+            0,
+            0,
+        )
+        .map_err(convert_err)?;
+        self.context = cairo::Context::new(&blank_surface).map_err(convert_err)?;
+
+        let stride = surface.stride() as usize;
+        let row_length = 4 * self.size.width as usize;
+
+        let buffer = surface.data()
+            .map(|buffer_raw| {
+                // pixels are native-endian words, with alpha in the highest byte, then red, green, and blue
+                buffer_raw.chunks(stride)
+                    .flat_map(|row| row[..row_length].chunks(4))
+                    .flat_map(|pixel| {
+                        let [a, r, g, b] = u32::from_ne_bytes([pixel[0], pixel[1], pixel[2], pixel[3]]).to_be_bytes();
+                        [r, g, b].map(|c| unpremultiply(c, a)).into_iter().chain([a])
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .map_err(convert_err);
+
+        // return surface to self
+        self.context = cairo::Context::new(&surface).map_err(convert_err)?;
+
+        buffer
     }
+
+    /// Clips to an area, within any existing clip, such as that of a context from a larger document.
+    /// Callers save the context first, so restoring it removes the clip.
     fn clip_area(&mut self, area: draw::Area) {
-        self.context.reset_clip();
         self.context.new_path();
 
         let points = [
@@ -503,7 +740,11 @@ fn font_to_cairo(name: draw::FontName) -> String {
         draw::FontName::FreeSans => "freesans".to_owned(),
         draw::FontName::Arial => "Arial".to_owned(),
         draw::FontName::Georgia => "Georgia".to_owned(),
+        draw::FontName::Times => "Times".to_owned(),
+        draw::FontName::Helvetica => "Helvetica".to_owned(),
+        draw::FontName::Courier => "Courier".to_owned(),
         draw::FontName::Custom(name) => name,
+        // fonts added to plt-draw fall back to the generic sans-serif family
         _ => "sans".to_owned(),
     }
 }
@@ -609,3 +850,150 @@ fn align_text(
 
     CairoPoint { x, y }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use draw::Canvas;
+
+    /// Saves a 30x20 canvas, black on its left half and the face color on its right half.
+    #[allow(dead_code)]
+    fn saved_bytes(face_color: draw::Color, format: draw::FileFormat, lossless: bool, quality: u8) -> Vec<u8> {
+        let mut canvas = CairoCanvas::new(draw::CanvasDescriptor {
+            size: draw::Size { width: 30, height: 20 },
+            face_color,
+            antialias: false,
+            ..Default::default()
+        })
+        .unwrap();
+        canvas.fill_region(draw::FillDescriptor {
+            points: vec![
+                draw::Point { x: 0.0, y: 0.0 },
+                draw::Point { x: 15.0, y: 0.0 },
+                draw::Point { x: 15.0, y: 20.0 },
+                draw::Point { x: 0.0, y: 20.0 },
+            ],
+            fill_color: draw::Color::BLACK,
+            clip_area: None,
+        })
+        .unwrap();
+
+        canvas.save_bytes(draw::SaveBytesDescriptor { format, dpi: 100, lossless, quality }).unwrap()
+    }
+
+    /// Reads a little-endian `u32` at an offset.
+    #[allow(dead_code)]
+    fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+    }
+
+    #[cfg(feature = "bmp")]
+    #[test]
+    fn bmp_density_and_pixels() {
+        for (face_color, bits_per_pixel) in [(draw::Color::WHITE, 24), (draw::Color::TRANSPARENT, 32)] {
+            let bytes = saved_bytes(face_color, draw::FileFormat::Bmp, true, 90);
+            assert_eq!(&bytes[..2], b"BM");
+            assert_eq!(u32_at(&bytes, 2) as usize, bytes.len());
+            assert_eq!(u32_at(&bytes, 28) & 0xffff, bits_per_pixel);
+            // 100 DPI is 3937 dots per meter
+            assert_eq!(u32_at(&bytes, 38), 3937);
+            assert_eq!(u32_at(&bytes, 42), 3937);
+
+            let image = image::load_from_memory_with_format(&bytes, image::ImageFormat::Bmp)
+                .unwrap()
+                .to_rgba8();
+            assert_eq!(image.dimensions(), (30, 20));
+            assert_eq!(image.get_pixel(2, 10).0, [0, 0, 0, 255]);
+            let face = if bits_per_pixel == 24 { [255, 255, 255, 255] } else { [0, 0, 0, 0] };
+            assert_eq!(image.get_pixel(27, 10).0, face);
+        }
+    }
+
+    #[cfg(feature = "tiff")]
+    #[test]
+    fn tiff_density_and_pixels() {
+        use tiff::decoder::{Decoder, DecodingResult};
+        use tiff::tags::Tag;
+
+        let bytes = saved_bytes(draw::Color::TRANSPARENT, draw::FileFormat::Tiff, true, 90);
+        let mut decoder = Decoder::new(std::io::Cursor::new(&bytes)).unwrap();
+        assert_eq!(decoder.dimensions().unwrap(), (30, 20));
+        assert_eq!(decoder.colortype().unwrap(), tiff::ColorType::RGBA(8));
+        assert_eq!(decoder.get_tag_u32_vec(Tag::XResolution).unwrap(), [100, 1]);
+        assert_eq!(decoder.get_tag_u32_vec(Tag::YResolution).unwrap(), [100, 1]);
+        // resolution in inches
+        assert_eq!(decoder.get_tag_u32_vec(Tag::ResolutionUnit).unwrap(), [2]);
+        // unassociated alpha
+        assert_eq!(decoder.get_tag_u32_vec(Tag::ExtraSamples).unwrap(), [2]);
+
+        let DecodingResult::U8(pixels) = decoder.read_image().unwrap() else {
+            panic!("expected 8-bit samples");
+        };
+        let pixel = |x: usize, y: usize| &pixels[(y * 30 + x) * 4..][..4];
+        assert_eq!(pixel(2, 10), [0, 0, 0, 255]);
+        assert_eq!(pixel(27, 10), [0, 0, 0, 0]);
+    }
+
+    #[cfg(feature = "jpeg")]
+    #[test]
+    fn jpeg_density_and_pixels() {
+        let bytes = saved_bytes(draw::Color::WHITE, draw::FileFormat::Jpeg, false, 90);
+        assert_eq!(&bytes[..2], &[0xff, 0xd8]);
+        // JFIF header, with 100 dots per inch
+        assert_eq!(&bytes[6..11], b"JFIF\0");
+        assert_eq!(&bytes[13..18], &[1, 0, 100, 0, 100]);
+
+        let mut decoder = jpeg_decoder::Decoder::new(&bytes[..]);
+        let pixels = decoder.decode().unwrap();
+        let info = decoder.info().unwrap();
+        assert_eq!((info.width, info.height), (30, 20));
+        assert_eq!(info.pixel_format, jpeg_decoder::PixelFormat::RGB24);
+        assert!(pixels[(10 * 30 + 2) * 3] < 16);
+        assert!(pixels[(10 * 30 + 27) * 3] > 239);
+
+        // lower quality compresses further
+        assert!(saved_bytes(draw::Color::WHITE, draw::FileFormat::Jpeg, false, 10).len() < bytes.len());
+        // quality 0 is clamped to the lowest quality the encoder accepts
+        assert_eq!(saved_bytes(draw::Color::WHITE, draw::FileFormat::Jpeg, false, 0), saved_bytes(draw::Color::WHITE, draw::FileFormat::Jpeg, false, 1));
+    }
+
+    #[cfg(feature = "webp")]
+    #[test]
+    fn webp_lossless_and_lossy() {
+        for (lossless, chunk) in [(true, b"VP8L"), (false, b"VP8 ")] {
+            let bytes = saved_bytes(draw::Color::WHITE, draw::FileFormat::WebP, lossless, 90);
+            assert_eq!(&bytes[..4], b"RIFF");
+            assert_eq!(&bytes[8..12], b"WEBP");
+            assert_eq!(&bytes[12..16], chunk);
+
+            let image = webp::Decoder::new(&bytes).decode().unwrap();
+            assert_eq!((image.width(), image.height()), (30, 20));
+
+            let stride = if image.is_alpha() { 4 } else { 3 };
+            let pixel = |x: usize, y: usize| image[(y * 30 + x) * stride];
+            assert!(pixel(2, 10) < 16);
+            assert!(pixel(27, 10) > 239);
+            if lossless {
+                assert!((0..20).all(|y| (0..30).all(|x| pixel(x, y) == if x < 15 { 0 } else { 255 })));
+            }
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "color channel `g` must be finite")]
+    fn non_finite_colors() {
+        let surface = cairo::ImageSurface::create(cairo::Format::ARgb32, 1, 1).unwrap();
+        let context = cairo::Context::new(&surface).unwrap();
+        set_source_color(&context, draw::Color { r: 0.0, g: f64::NAN, b: 0.0, a: 1.0 });
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "color channel `a` must be from 0.0 to 1.0")]
+    fn out_of_range_colors() {
+        let surface = cairo::ImageSurface::create(cairo::Format::ARgb32, 1, 1).unwrap();
+        let context = cairo::Context::new(&surface).unwrap();
+        set_source_color(&context, draw::Color { r: 0.0, g: 0.0, b: 0.0, a: 1.5 });
+    }
+}
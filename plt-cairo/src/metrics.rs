@@ -0,0 +1,71 @@
+//! Fixed text metrics, so text is measured the same whichever fonts are installed.
+//!
+//! Widths are the advance widths of regular Helvetica, which FreeSans, Arial, and Liberation Sans share,
+//! and Courier, which is monospaced.
+//! Every other font, including Times and Georgia, and all bold or italic text are measured as regular Helvetica,
+//! so their measured sizes can differ from what is drawn.
+//!
+//! Only layout is reproducible this way. Glyphs are still drawn with the installed fonts,
+//! so rendered pixels can differ between machines.
+
+/// Advance widths of printable ASCII characters, from space to tilde, in thousandths of an em.
+const ASCII_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, // space to /
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, // 0 to 9
+    278, 278, 584, 584, 584, 556, 1015, // : to @
+    667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, // A to M
+    722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, // N to Z
+    278, 278, 278, 469, 556, 333, // [ to `
+    556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, // a to m
+    556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, // n to z
+    334, 260, 334, 584, // { to ~
+];
+/// Advance width of superscript digits and signs, in thousandths of an em.
+const SUPERSCRIPT_WIDTH: u16 = 333;
+/// Advance width of other characters, in thousandths of an em.
+const DEFAULT_WIDTH: u16 = 556;
+/// Advance width of every Courier character, in thousandths of an em.
+const COURIER_WIDTH: u16 = 600;
+
+/// Height above the baseline of capitals, digits, and tall lowercase letters, in ems.
+const CAP_HEIGHT: f64 = 0.729;
+/// Height above the baseline of short lowercase letters, in ems.
+const X_HEIGHT: f64 = 0.538;
+/// Depth below the baseline of descenders, in ems.
+const DESCENT: f64 = 0.212;
+
+/// The width and height of text at a font size, in the units of the font size.
+///
+/// Widths are summed advance widths, and heights span from the lowest descender to the highest character,
+/// matching the ink extents Cairo measures closely enough for layout.
+pub(crate) fn text_size(text: &str, font_name: &draw::FontName, font_size: f64) -> (f64, f64) {
+    let mut width = 0;
+    let (mut top, mut bottom) = (0.0f64, 0.0f64);
+
+    for c in text.chars() {
+        width += match c {
+            _ if matches!(font_name, draw::FontName::Courier) => COURIER_WIDTH,
+            ' '..='~' => ASCII_WIDTHS[c as usize - ' ' as usize],
+            '⁰' | '¹' | '²' | '³' | '⁴'..='⁹' | '⁺' | '⁻' => SUPERSCRIPT_WIDTH,
+            _ => DEFAULT_WIDTH,
+        } as u32;
+
+        if c.is_whitespace() {
+            continue;
+        }
+
+        top = top.max(match c {
+            'a' | 'c' | 'e' | 'g' | 'm'..='s' | 'u'..='z' => X_HEIGHT,
+            '-' | '+' | '=' | '~' | '*' => X_HEIGHT,
+            ',' | '.' | '_' => 0.0,
+            _ => CAP_HEIGHT,
+        });
+        bottom = bottom.max(match c {
+            'g' | 'j' | 'p' | 'q' | 'y' | ',' | ';' | '_' => DESCENT,
+            '(' | ')' | '[' | ']' | '{' | '}' | '|' | '@' => DESCENT,
+            _ => 0.0,
+        });
+    }
+
+    (width as f64 / 1000.0 * font_size, (top + bottom) * font_size)
+}
@@ -15,6 +15,8 @@ pub enum DrawError {
     UnsupportedImageFormat(String),
     #[error("{0}")]
     UnsupportedShape(String),
+    #[error("{0}")]
+    InvalidColor(String),
 }
 
 /// 2D size in dot (pixel) numbers.
@@ -69,7 +71,7 @@ impl Area {
 }
 
 /// An RGBA float representation of a color.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Color {
     /// Amount of red, from 0.0 to 1.0.
     pub r: f64,
@@ -90,6 +92,275 @@ impl Color {
     pub const GREEN: Color = Self { r: 0.0, g: 1.0, b: 0.0, a: 1.0, };
     pub const BLUE: Color = Self { r: 0.0, g: 0.0, b: 1.0, a: 1.0, };
     pub const PURPLE: Color = Self { r: 0.62, g: 0.12, b: 0.94, a: 1.0, };
+
+    /// Creates a color, clamping each channel to the range 0.0 to 1.0.
+    ///
+    /// NaN channels are set to 0.0.
+    pub fn new(r: f64, g: f64, b: f64, a: f64) -> Self {
+        let clamp = |value: f64| if value.is_nan() { 0.0 } else { value.clamp(0.0, 1.0) };
+
+        Self { r: clamp(r), g: clamp(g), b: clamp(b), a: clamp(a) }
+    }
+
+    /// Creates a color, returning an error if any channel is outside the range 0.0 to 1.0.
+    pub fn checked_new(r: f64, g: f64, b: f64, a: f64) -> Result<Self, DrawError> {
+        let color = Self { r, g, b, a };
+
+        for (name, value) in [("red", r), ("green", g), ("blue", b), ("alpha", a)] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(DrawError::InvalidColor(format!(
+                    "{name} channel of {color:?} is {value}, but must be from 0.0 to 1.0",
+                )));
+            }
+        }
+
+        Ok(color)
+    }
+
+    /// Creates an opaque color from 8-bit channels, from 0 to 255.
+    pub fn from_rgb8(r: u8, g: u8, b: u8) -> Self {
+        Self::from_rgba8(r, g, b, u8::MAX)
+    }
+
+    /// Creates a color from 8-bit channels, including alpha, from 0 to 255.
+    pub fn from_rgba8(r: u8, g: u8, b: u8, a: u8) -> Self {
+        let channel = |value: u8| value as f64 / 255.0;
+
+        Self { r: channel(r), g: channel(g), b: channel(b), a: channel(a) }
+    }
+
+    /// Creates a color from a hex code, such as `#1f77b4`.
+    ///
+    /// Codes are case-insensitive, with an optional leading `#`, and may be `RRGGBB` or `RRGGBBAA`,
+    /// or the shorthands `RGB` and `RGBA`, in which each digit is repeated.
+    /// Colors without an alpha channel are opaque.
+    ///
+    /// ```rust
+    ///# use plt_draw::Color;
+    ///assert_eq!(Color::from_hex("#FF8000").unwrap(), Color::from_rgb8(255, 128, 0));
+    ///assert_eq!(Color::from_hex("ff800080").unwrap(), Color::from_rgba8(255, 128, 0, 128));
+    ///assert_eq!(Color::from_hex("#f80").unwrap(), Color::from_rgb8(255, 136, 0));
+    ///assert!(Color::from_hex("#ff80").is_ok());
+    ///
+    ///// every 8-bit channel value round trips through a hex code
+    ///for value in 0..=255 {
+    ///    let hex = format!("#{value:02x}{value:02X}{value:02x}{value:02X}");
+    ///    assert_eq!(Color::from_hex(&hex).unwrap(), Color::from_rgba8(value, value, value, value));
+    ///}
+    ///
+    ///assert!(Color::from_hex("#ff800").is_err());
+    ///assert!(Color::from_hex("#gg8000").is_err());
+    /// ```
+    pub fn from_hex(hex: &str) -> Result<Self, DrawError> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        let invalid = || DrawError::InvalidColor(format!(
+            "`{hex}` is not a hex color, which must be RGB, RGBA, RRGGBB, or RRGGBBAA",
+        ));
+
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let channel = |i: usize, len: usize| -> u8 {
+            let value = u8::from_str_radix(&digits[(i * len)..((i + 1) * len)], 16).unwrap();
+            // shorthand digits are repeated, so f becomes ff
+            if len == 1 { value * 0x11 } else { value }
+        };
+
+        let (len, alpha) = match digits.len() {
+            3 => (1, false),
+            4 => (1, true),
+            6 => (2, false),
+            8 => (2, true),
+            _ => return Err(invalid()),
+        };
+        let a = if alpha { channel(3, len) } else { u8::MAX };
+
+        Ok(Self::from_rgba8(channel(0, len), channel(1, len), channel(2, len), a))
+    }
+
+    /// Creates an opaque color from hue in degrees, and saturation and lightness from 0.0 to 1.0.
+    ///
+    /// ```rust
+    ///# use plt_draw::Color;
+    ///// a color cycle of evenly spaced hues
+    ///let n = 6;
+    ///let cycle: Vec<Color> = (0..n)
+    ///    .map(|i| Color::from_hsl((i * 360 / n) as f64, 0.6, 0.5))
+    ///    .collect();
+    /// ```
+    pub fn from_hsl(h: f64, s: f64, l: f64) -> Self {
+        Self::from_hsla(h, s, l, 1.0)
+    }
+
+    /// Creates a color from hue in degrees, and saturation, lightness, and alpha from 0.0 to 1.0.
+    pub fn from_hsla(h: f64, s: f64, l: f64, a: f64) -> Self {
+        let (s, l) = (s.clamp(0.0, 1.0), l.clamp(0.0, 1.0));
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+
+        Self::from_hue_chroma(h, chroma, l - chroma / 2.0, a)
+    }
+
+    /// Creates an opaque color from hue in degrees, and saturation and value from 0.0 to 1.0.
+    pub fn from_hsv(h: f64, s: f64, v: f64) -> Self {
+        Self::from_hsva(h, s, v, 1.0)
+    }
+
+    /// Creates a color from hue in degrees, and saturation, value, and alpha from 0.0 to 1.0.
+    pub fn from_hsva(h: f64, s: f64, v: f64, a: f64) -> Self {
+        let (s, v) = (s.clamp(0.0, 1.0), v.clamp(0.0, 1.0));
+        let chroma = v * s;
+
+        Self::from_hue_chroma(h, chroma, v - chroma, a)
+    }
+
+    /// Linearly interpolates each channel, including alpha, from this color to another.
+    ///
+    /// The fraction `t` is clamped to the range 0.0 to 1.0, where 0.0 gives this color.
+    pub fn lerp(self, other: Color, t: f64) -> Color {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let lerp = |from: f64, to: f64| from + (to - from) * t;
+
+        Self {
+            r: lerp(self.r, other.r),
+            g: lerp(self.g, other.g),
+            b: lerp(self.b, other.b),
+            a: lerp(self.a, other.a),
+        }
+    }
+
+    /// Whether every channel is within the range 0.0 to 1.0.
+    pub fn is_valid(&self) -> bool {
+        [self.r, self.g, self.b, self.a].iter().all(|value| (0.0..=1.0).contains(value))
+    }
+}
+impl Color {
+    /// Converts hue in degrees, chroma, and a lightness offset to a color.
+    fn from_hue_chroma(h: f64, chroma: f64, offset: f64, a: f64) -> Self {
+        // hues wrap around, in six sectors of 60 degrees
+        let sector = h.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+
+        let (r, g, b) = match sector as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+
+        Self::new(r + offset, g + offset, b + offset, a)
+    }
+}
+
+/// Maps values from 0.0 to 1.0 to colors.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug)]
+pub enum Colormap {
+    /// Diverging map from dark red, through near white, to dark blue.
+    RdBu,
+    /// Diverging map from blue, through light gray, to red, with even perceived lightness changes.
+    Coolwarm,
+    /// Sequential map from dark purple, through blue and green, to yellow,
+    /// with even perceived lightness changes.
+    Viridis,
+    /// Sequential map from dark blue, through purple and orange, to yellow,
+    /// with even perceived lightness changes.
+    Plasma,
+    /// Sequential map from black to white.
+    Grayscale,
+}
+impl Colormap {
+    /// Gets the color at `t`, which is clamped to the range 0.0 to 1.0.
+    pub fn sample(&self, t: f64) -> Color {
+        let stops = self.stops();
+
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let position = t * (stops.len() - 1) as f64;
+        let index = (position.floor() as usize).min(stops.len() - 2);
+
+        stops[index].lerp(stops[index + 1], position - index as f64)
+    }
+}
+impl Colormap {
+    /// Colors evenly spaced from 0.0 to 1.0.
+    fn stops(&self) -> &'static [Color] {
+        const fn rgb(r: u8, g: u8, b: u8) -> Color {
+            Color { r: r as f64 / 255.0, g: g as f64 / 255.0, b: b as f64 / 255.0, a: 1.0 }
+        }
+
+        // from ColorBrewer
+        const RDBU: [Color; 11] = [
+            rgb(0x67, 0x00, 0x1f), rgb(0xb2, 0x18, 0x2b), rgb(0xd6, 0x60, 0x4d),
+            rgb(0xf4, 0xa5, 0x82), rgb(0xfd, 0xdb, 0xc7), rgb(0xf7, 0xf7, 0xf7),
+            rgb(0xd1, 0xe5, 0xf0), rgb(0x92, 0xc5, 0xde), rgb(0x43, 0x93, 0xc3),
+            rgb(0x21, 0x66, 0xac), rgb(0x05, 0x30, 0x61),
+        ];
+        // from Moreland, "Diverging Color Maps for Scientific Visualization"
+        const COOLWARM: [Color; 9] = [
+            rgb(59, 76, 192), rgb(98, 130, 234), rgb(141, 176, 254),
+            rgb(184, 208, 249), rgb(221, 221, 221), rgb(245, 196, 173),
+            rgb(244, 154, 123), rgb(222, 96, 77), rgb(180, 4, 38),
+        ];
+
+        // from matplotlib, sampled at 9 even steps
+        const VIRIDIS: [Color; 9] = [
+            rgb(0x44, 0x01, 0x54), rgb(0x47, 0x2d, 0x7b), rgb(0x3b, 0x52, 0x8b),
+            rgb(0x2c, 0x72, 0x8e), rgb(0x21, 0x91, 0x8c), rgb(0x28, 0xae, 0x80),
+            rgb(0x5e, 0xc9, 0x62), rgb(0xad, 0xdc, 0x30), rgb(0xfd, 0xe7, 0x25),
+        ];
+        const PLASMA: [Color; 9] = [
+            rgb(0x0d, 0x08, 0x87), rgb(0x4c, 0x02, 0xa1), rgb(0x7e, 0x03, 0xa8),
+            rgb(0xa9, 0x23, 0x95), rgb(0xcc, 0x47, 0x78), rgb(0xe5, 0x6b, 0x5d),
+            rgb(0xf8, 0x95, 0x40), rgb(0xfd, 0xc5, 0x27), rgb(0xf0, 0xf9, 0x21),
+        ];
+        const GRAYSCALE: [Color; 2] = [rgb(0, 0, 0), rgb(255, 255, 255)];
+
+        match self {
+            Colormap::RdBu => &RDBU,
+            Colormap::Coolwarm => &COOLWARM,
+            Colormap::Viridis => &VIRIDIS,
+            Colormap::Plasma => &PLASMA,
+            Colormap::Grayscale => &GRAYSCALE,
+        }
+    }
+}
+
+/// How data values are mapped to the range 0.0 to 1.0 of a [`Colormap`].
+#[derive(Copy, Clone, Debug, Default)]
+pub enum Normalization {
+    /// The minimum maps to 0.0, and the maximum maps to 1.0.
+    #[default]
+    Linear,
+    /// The center maps to 0.5, with equal distances on either side of the center mapping to
+    /// equal distances from 0.5.
+    ///
+    /// The data extreme furthest from the center maps to 0.0 or 1.0.
+    Diverging { center: f64 },
+}
+impl Normalization {
+    /// Maps a value to the range 0.0 to 1.0, given the minimum and maximum of the data.
+    pub fn normalize(&self, value: f64, min: f64, max: f64) -> f64 {
+        let t = match *self {
+            Normalization::Linear => {
+                if max > min {
+                    (value - min) / (max - min)
+                } else {
+                    0.5
+                }
+            },
+            Normalization::Diverging { center } => {
+                let half_range = f64::max((max - center).abs(), (center - min).abs());
+                if half_range > 0.0 {
+                    0.5 + 0.5 * (value - center) / half_range
+                } else {
+                    0.5
+                }
+            },
+        };
+
+        t.clamp(0.0, 1.0)
+    }
 }
 
 /// A drawable shape.
@@ -99,6 +370,16 @@ pub enum Shape {
     Circle { r: u32 },
     Square { l: u32 },
     Rectangle { h: u32, w: u32 },
+    /// An equilateral triangle pointing up, with sides of length `l`.
+    TriangleUp { l: u32 },
+    /// An equilateral triangle pointing down, with sides of length `l`.
+    TriangleDown { l: u32 },
+    /// A square rotated onto its corner, with diagonals of length `l`.
+    Diamond { l: u32 },
+    /// A plus sign, with arms `thickness` wide spanning a length of `l`.
+    Plus { l: u32, thickness: u32 },
+    /// A plus sign rotated into an x, with arms `thickness` wide spanning a length of `l`.
+    Cross { l: u32, thickness: u32 },
 }
 impl Shape {
     /// Scales the shape by some multiplicative factor.
@@ -107,6 +388,11 @@ impl Shape {
             Shape::Circle { r } => Shape::Circle { r: mult * *r },
             Shape::Square { l } => Shape::Square { l: mult * *l },
             Shape::Rectangle { h, w } => Shape::Rectangle { h: mult * *h, w: mult * *w },
+            Shape::TriangleUp { l } => Shape::TriangleUp { l: mult * *l },
+            Shape::TriangleDown { l } => Shape::TriangleDown { l: mult * *l },
+            Shape::Diamond { l } => Shape::Diamond { l: mult * *l },
+            Shape::Plus { l, thickness } => Shape::Plus { l: mult * *l, thickness: mult * *thickness },
+            Shape::Cross { l, thickness } => Shape::Cross { l: mult * *l, thickness: mult * *thickness },
         }
     }
 }
@@ -135,44 +421,44 @@ impl Default for Font {
 }
 
 /// The name of a text font.
+///
+/// Backends find the closest installed font, so a missing font falls back to a similar one.
 #[non_exhaustive]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub enum FontName {
+    /// GNU FreeSans, a sans-serif font.
+    #[default]
     FreeSans,
+    /// Arial, a sans-serif font.
     Arial,
+    /// Georgia, a serif font.
     Georgia,
+    /// Times, a serif font.
+    Times,
+    /// Helvetica, a sans-serif font.
+    Helvetica,
+    /// Courier, a monospace font.
+    Courier,
+    /// Any other font, by its family name.
     Custom(String),
 }
-impl Default for FontName {
-    fn default() -> Self {
-        Self::FreeSans
-    }
-}
 
 /// The slant of a font.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Default)]
 pub enum FontSlant {
+    #[default]
     Normal,
     Italic,
     Oblique,
 }
-impl Default for FontSlant {
-    fn default() -> Self {
-        Self::Normal
-    }
-}
 
 /// The weight of a font.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Default)]
 pub enum FontWeight {
+    #[default]
     Normal,
     Bold,
 }
-impl Default for FontWeight {
-    fn default() -> Self {
-        Self::Normal
-    }
-}
 
 /// How something should be aligned.
 #[derive(Copy, Clone, Debug)]
@@ -205,6 +491,26 @@ pub enum FileFormat {
     Png,
     /// An SVG file format.
     Svg,
+    /// A BMP file format.
+    Bmp,
+    /// A TIFF file format.
+    Tiff,
+    /// A WebP file format.
+    WebP,
+    /// A JPEG file format, without transparency.
+    Jpeg,
+    /// A PDF file format.
+    Pdf,
+}
+
+/// An in-memory bitmap image.
+#[derive(Clone, Debug)]
+pub struct Bitmap {
+    /// The size in dots (pixels) of the image.
+    pub size: Size,
+    /// RGBA pixel data, 4 bytes per pixel, in row major order from the top left.
+    /// Colors are not premultiplied by alpha.
+    pub data: Vec<u8>,
 }
 
 /// Describes a [`Canvas`] to be constructed.
@@ -216,6 +522,8 @@ pub struct CanvasDescriptor {
     pub face_color: Color,
     /// What type of image format will be drawn.
     pub image_format: ImageFormat,
+    /// Whether the edges of lines, shapes, and text are anti-aliased.
+    pub antialias: bool,
 }
 impl Default for CanvasDescriptor {
     fn default() -> Self {
@@ -223,6 +531,7 @@ impl Default for CanvasDescriptor {
             size: Size { height: 100, width: 100 },
             face_color: Color::WHITE,
             image_format: ImageFormat::Bitmap,
+            antialias: true,
         }
     }
 }
@@ -234,6 +543,8 @@ pub enum ImageFormat {
     Bitmap,
     /// An image represented as an SVG image.
     Svg,
+    /// An image represented as a PDF document.
+    Pdf,
 }
 
 /// Describes a shape to be drawn.
@@ -373,6 +684,23 @@ pub struct SaveFileDescriptor<P: AsRef<path::Path>> {
     pub format: FileFormat,
     /// The dots (pixels) per inch.
     pub dpi: u16,
+    /// Whether formats supporting lossy compression should be encoded losslessly.
+    pub lossless: bool,
+    /// The encoding quality, from 0 to 100.
+    pub quality: u8,
+}
+
+/// Describes how to encode the image to in-memory bytes.
+#[derive(Clone, Debug)]
+pub struct SaveBytesDescriptor {
+    /// The image format of the bytes.
+    pub format: FileFormat,
+    /// The dots (pixels) per inch.
+    pub dpi: u16,
+    /// Whether formats supporting lossy compression should be encoded losslessly.
+    pub lossless: bool,
+    /// The encoding quality, from 0 to 100.
+    pub quality: u8,
 }
 
 /// Represents a structure used for drawing.
@@ -396,6 +724,18 @@ pub trait Canvas {
         &mut self,
         desc: SaveFileDescriptor<P>,
     ) -> Result<(), DrawError>;
+    /// Encode the image to in-memory bytes, as they would be saved to a file.
+    fn save_bytes(&mut self, _desc: SaveBytesDescriptor) -> Result<Vec<u8>, DrawError> {
+        Err(DrawError::UnsupportedFileFormat(
+            "in-memory encoding is not supported by this backend".to_string()
+        ))
+    }
     /// Get canvas size.
     fn size(&self) -> Result<Size, DrawError>;
+    /// Get the drawn image as an in-memory [`Bitmap`].
+    fn bitmap(&mut self) -> Result<Bitmap, DrawError> {
+        Err(DrawError::UnsupportedImageFormat(
+            "in-memory bitmaps are not supported by this backend".to_string()
+        ))
+    }
 }
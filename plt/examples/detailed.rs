@@ -41,8 +41,7 @@ fn main() {
     subplot.plotter()
         .line(None)
         .marker(Some(MarkerStyle::Circle))
-        .marker_color(Color::TRANSPARENT)
-        .marker_outline(true)
+        .marker_fill(false)
         .marker_outline_color(Color::BLACK)
         .plot(&xs, &scatter_ys)
         .unwrap();
@@ -12,10 +12,8 @@ fn main() {
         .map(|_| 10.0 * norm.sample(&mut rand::thread_rng()))
         .collect();
 
-    // histogram data
-    let bin_edges = ndarray::Array1::linspace(-6.0, 6.0, 31);
-    let xhist = histogram(&xs, bin_edges.as_slice().unwrap());
-    let yhist = histogram(&ys, bin_edges.as_slice().unwrap());
+    // histogram bins
+    let bin_edges = ndarray::Array1::linspace(-6.0, 6.0, 31).to_vec();
 
     // create center subplot
     let mut center_sp = Subplot::builder()
@@ -33,8 +31,7 @@ fn main() {
     center_sp.plotter()
         .line(None)
         .marker(Some(MarkerStyle::Circle))
-        .marker_color(Color::TRANSPARENT)
-        .marker_outline(true)
+        .marker_fill(false)
         .marker_outline_color(Color::BLACK)
         .plot(&xs, &ys)
         .unwrap();
@@ -64,11 +61,10 @@ fn main() {
         .minor_tick_marks(Axes::BothSecondary, TickSpacing::None)
         .build();
 
-    // step plot the x-value histogram
-    top_sp.plotter()
-        .line_color(Color::BLACK)
-        .line_width(2)
-        .step(&bin_edges, &xhist)
+    // histogram the x-values
+    top_sp.filler()
+        .edge(LineStyle::Solid, Some(Color::BLACK))
+        .hist(&xs, bin_edges.clone())
         .unwrap();
 
     // create right subplot
@@ -77,18 +73,18 @@ fn main() {
             font_size: 16.0,
             ..Default::default()
         })
-        .xlimits(Limits::Manual { min: -6.0, max: 6.0 })
-        .ylimits(Limits::Manual { min: 0.0, max: 500.0 })
+        .xlimits(Limits::Manual { min: 0.0, max: 500.0 })
+        .ylimits(Limits::Manual { min: -6.0, max: 6.0 })
         .minor_tick_marks(Axes::BothPrimary, TickSpacing::Count(1))
         .major_tick_marks(Axes::BothSecondary, TickSpacing::None)
         .minor_tick_marks(Axes::BothSecondary, TickSpacing::None)
         .build();
 
-    // step plot the y-value histogram
-    right_sp.plotter()
-        .line_color(Color::BLACK)
-        .line_width(2)
-        .step(&bin_edges, &yhist)
+    // histogram the y-values, along the side of the center subplot
+    right_sp.filler()
+        .edge(LineStyle::Solid, Some(Color::BLACK))
+        .orientation(Orientation::Horizontal)
+        .hist(&ys, bin_edges)
         .unwrap();
 
     // setup the layout
@@ -104,20 +100,3 @@ fn main() {
     // save figure to file
     fig.draw_file(FileFormat::Png, "example.png").unwrap();
 }
-
-// returns a new histogram with bins defined by `bin_edges` and filled from `data`.
-fn histogram(data: &[f64], bin_edges: &[f64]) -> ndarray::Array1<f64> {
-    let mut hist = ndarray::Array1::from_elem(bin_edges.len() - 1, 10.0);
-
-    for d in data {
-        for (i, bin) in bin_edges.windows(2).enumerate() {
-            let lower = bin[0];
-            let upper = bin[1];
-            if (lower..upper).contains(d) {
-                hist[i] += 1.0;
-            }
-        }
-    }
-
-    hist
-}
@@ -0,0 +1,168 @@
+use crate::backend;
+use crate::{Bitmap, Figure, PltError};
+
+use std::{path, time};
+#[cfg(any(feature = "gif", feature = "apng"))]
+use std::{fs, io};
+
+#[cfg(feature = "apng")]
+mod apng;
+#[cfg(feature = "gif")]
+mod gif;
+
+/// A sequence of rendered figures, which can be saved as an animated image.
+///
+/// Every frame must have the same size as the first frame.
+#[derive(Clone, Debug)]
+pub struct FigureAnimation {
+    frames: Vec<Bitmap>,
+    frame_delay: time::Duration,
+    looping: bool,
+}
+impl FigureAnimation {
+    /// Returns a builder with default configuration.
+    pub fn builder() -> FigureAnimationBuilder {
+        FigureAnimationBuilder { animation: Self::default() }
+    }
+
+    /// Renders a figure and adds it as the next frame.
+    pub fn add_frame<B: backend::Canvas>(&mut self, figure: &Figure<B>) -> Result<(), PltError> {
+        self.add_bitmap(figure.draw_bitmap()?)
+    }
+
+    /// Adds an already rendered [`Bitmap`] as the next frame.
+    ///
+    /// Frames with a different size from the first frame are rejected.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let bitmap = |width, height| Bitmap {
+    ///     size: backend::Size { width, height },
+    ///     data: vec![255; 4 * width as usize * height as usize],
+    /// };
+    ///
+    /// let mut animation = FigureAnimation::builder().build();
+    /// animation.add_bitmap(bitmap(4, 3)).unwrap();
+    /// animation.add_bitmap(bitmap(4, 3)).unwrap();
+    /// assert!(matches!(
+    ///     animation.add_bitmap(bitmap(3, 4)),
+    ///     Err(PltError::InvalidFrameSize { expected: (4, 3), found: (3, 4) }),
+    /// ));
+    /// assert_eq!(animation.frames().len(), 2);
+    /// ```
+    pub fn add_bitmap(&mut self, bitmap: Bitmap) -> Result<(), PltError> {
+        let (width, height) = (bitmap.size.width, bitmap.size.height);
+
+        if bitmap.data.len() != 4 * width as usize * height as usize {
+            return Err(PltError::InvalidData(
+                "bitmap data does not match its size".to_owned()
+            ));
+        }
+        if let Some(first) = self.frames.first() {
+            if (first.size.width, first.size.height) != (width, height) {
+                return Err(PltError::InvalidFrameSize {
+                    expected: (first.size.width, first.size.height),
+                    found: (width, height),
+                });
+            }
+        }
+
+        self.frames.push(bitmap);
+
+        Ok(())
+    }
+
+    /// Get reference to the held frames.
+    pub fn frames(&self) -> &[Bitmap] {
+        &self.frames
+    }
+
+    /// Removes all frames from the animation.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    /// Draw animation to a file.
+    #[cfg_attr(not(any(feature = "gif", feature = "apng")), allow(unused_variables))]
+    pub fn draw_file<P: AsRef<path::Path>>(
+        &self,
+        format: AnimationFormat,
+        filename: P,
+    ) -> Result<(), PltError> {
+        if self.frames.is_empty() {
+            return Err(PltError::InvalidData("animation has no frames".to_owned()));
+        }
+
+        match format {
+            #[cfg(feature = "gif")]
+            AnimationFormat::Gif => {
+                let file = fs::File::create(filename)?;
+                let w = &mut io::BufWriter::new(file);
+
+                Ok(gif::encode(w, &self.frames, self.frame_delay, self.looping)?)
+            },
+            #[cfg(not(feature = "gif"))]
+            AnimationFormat::Gif => Err(PltError::DrawError(
+                draw::DrawError::UnsupportedFileFormat("gif feature is not enabled".to_string())
+            )),
+            #[cfg(feature = "apng")]
+            AnimationFormat::Apng => {
+                let file = fs::File::create(filename)?;
+                let w = &mut io::BufWriter::new(file);
+
+                Ok(apng::encode(w, &self.frames, self.frame_delay, self.looping)?)
+            },
+            #[cfg(not(feature = "apng"))]
+            AnimationFormat::Apng => Err(PltError::DrawError(
+                draw::DrawError::UnsupportedFileFormat("apng feature is not enabled".to_string())
+            )),
+        }
+    }
+}
+impl Default for FigureAnimation {
+    fn default() -> Self {
+        Self {
+            frames: Vec::new(),
+            frame_delay: time::Duration::from_millis(100),
+            looping: true,
+        }
+    }
+}
+
+/// Builds and sets the configuration for a [`FigureAnimation`].
+pub struct FigureAnimationBuilder {
+    animation: FigureAnimation,
+}
+impl FigureAnimationBuilder {
+    /// Builds the animation.
+    pub fn build(self) -> FigureAnimation {
+        self.animation
+    }
+
+    /// Sets how long each frame is shown.
+    ///
+    /// GIF files store delays in hundredths of a second.
+    pub fn frame_delay(mut self, delay: time::Duration) -> Self {
+        self.animation.frame_delay = delay;
+
+        self
+    }
+
+    /// Sets whether the animation repeats forever, or plays once.
+    pub fn looping(mut self, looping: bool) -> Self {
+        self.animation.looping = looping;
+
+        self
+    }
+}
+
+/// An animated image file format.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug)]
+pub enum AnimationFormat {
+    /// An animated GIF file format, limited to 256 colors in each frame.
+    Gif,
+    /// An animated PNG file format.
+    Apng,
+}
@@ -0,0 +1,35 @@
+use crate::Bitmap;
+
+use std::{io, time};
+
+/// Encodes frames as an animated PNG.
+pub(super) fn encode<W: io::Write>(
+    w: &mut W,
+    frames: &[Bitmap],
+    frame_delay: time::Duration,
+    looping: bool,
+) -> io::Result<()> {
+    let size = frames[0].size;
+
+    let mut encoder = png::Encoder::new(w, size.width, size.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_animated(frames.len() as u32, if looping { 0 } else { 1 })?;
+
+    // delays are stored as a fraction of seconds
+    let millis = frame_delay.as_millis();
+    let (numerator, denominator) = if millis <= u16::MAX as u128 {
+        (millis as u16, 1000)
+    } else {
+        ((millis / 10).min(u16::MAX as u128) as u16, 100)
+    };
+    encoder.set_frame_delay(numerator, denominator)?;
+
+    let mut writer = encoder.write_header()?;
+    for frame in frames {
+        writer.write_image_data(&frame.data)?;
+    }
+    writer.finish()?;
+
+    Ok(())
+}
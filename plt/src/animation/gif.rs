@@ -0,0 +1,136 @@
+use crate::Bitmap;
+
+use std::{io, time};
+
+/// Pixels with alpha below this are written as transparent.
+const ALPHA_THRESHOLD: u8 = 128;
+/// Quantization speed for frames with more than 256 colors, from 1 (best) to 30 (fastest).
+const QUANTIZE_SPEED: i32 = 10;
+
+/// Encodes frames as an animated GIF.
+///
+/// Each frame has its own color table. Frames with up to 256 colors keep their exact colors,
+/// while frames with more colors are quantized with NeuQuant.
+pub(super) fn encode<W: io::Write>(
+    w: &mut W,
+    frames: &[Bitmap],
+    frame_delay: time::Duration,
+    looping: bool,
+) -> io::Result<()> {
+    let size = frames[0].size;
+    let (width, height) = match (u16::try_from(size.width), u16::try_from(size.height)) {
+        (Ok(width), Ok(height)) => (width, height),
+        _ => return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "gif images are limited to 65535 pixels in each dimension",
+        )),
+    };
+
+    let mut encoder = gif::Encoder::new(w, width, height, &[]).map_err(convert_err)?;
+    if looping {
+        encoder.set_repeat(gif::Repeat::Infinite).map_err(convert_err)?;
+    }
+
+    let delay = (frame_delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+
+    for frame in frames {
+        // GIF pixels are either opaque or fully transparent, with one transparent color
+        let mut pixels = frame.data.clone();
+        let mut transparent = false;
+        for pixel in pixels.chunks_exact_mut(4) {
+            if pixel[3] < ALPHA_THRESHOLD {
+                pixel.copy_from_slice(&[0; 4]);
+                transparent = true;
+            } else {
+                pixel[3] = 255;
+            }
+        }
+
+        let mut frame = gif::Frame::from_rgba_speed(width, height, &mut pixels, QUANTIZE_SPEED);
+        frame.delay = delay;
+        // clear transparent frames before the next is drawn
+        frame.dispose = if transparent { gif::DisposalMethod::Background } else { gif::DisposalMethod::Keep };
+
+        encoder.write_frame(&frame).map_err(convert_err)?;
+    }
+
+    Ok(())
+}
+
+/// Converts a GIF encoding error into an I/O error.
+fn convert_err(err: gif::EncodingError) -> io::Error {
+    match err {
+        gif::EncodingError::Io(err) => err,
+        err => io::Error::new(io::ErrorKind::InvalidInput, err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encodes frames and decodes them back to RGBA, with each frame's delay.
+    fn round_trip(frames: &[Bitmap], looping: bool) -> (Vec<(Vec<u8>, u16)>, gif::Repeat) {
+        let mut bytes = Vec::new();
+        encode(&mut bytes, frames, time::Duration::from_millis(50), looping).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(&bytes[..]).unwrap();
+
+        let mut decoded = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            decoded.push((frame.buffer.to_vec(), frame.delay));
+        }
+
+        (decoded, decoder.repeat())
+    }
+
+    #[test]
+    fn exact_colors_and_timing() {
+        let frames = [
+            Bitmap {
+                size: draw::Size { width: 3, height: 1 },
+                data: vec![255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255],
+            },
+            Bitmap {
+                size: draw::Size { width: 3, height: 1 },
+                data: vec![0, 0, 255, 255, 255, 0, 0, 255, 0, 255, 0, 255],
+            },
+        ];
+
+        let (decoded, repeat) = round_trip(&frames, true);
+        assert_eq!(repeat, gif::Repeat::Infinite);
+        assert_eq!(decoded.len(), 2);
+        for ((data, delay), frame) in decoded.iter().zip(&frames) {
+            assert_eq!(data, &frame.data);
+            assert_eq!(*delay, 5);
+        }
+
+        let (_, repeat) = round_trip(&frames[..1], false);
+        assert_eq!(repeat, gif::Repeat::Finite(0));
+    }
+
+    #[test]
+    fn transparency() {
+        let frame = Bitmap {
+            size: draw::Size { width: 3, height: 1 },
+            data: vec![255, 0, 0, 255, 0, 255, 0, 200, 0, 0, 255, 100],
+        };
+
+        let (decoded, _) = round_trip(&[frame], false);
+        assert_eq!(decoded[0].0, [255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn quantized_colors() {
+        // gradients of more colors than fit, quantized to nearby colors
+        let data = (0..512 * 64u32).flat_map(|v| [v as u8, (v / 2) as u8, 0, 255]).collect::<Vec<_>>();
+        let frame = Bitmap { size: draw::Size { width: 512, height: 64 }, data };
+
+        let (decoded, _) = round_trip(std::slice::from_ref(&frame), false);
+        for (found, expected) in decoded[0].0.iter().zip(&frame.data) {
+            assert!((*found as i32 - *expected as i32).abs() <= 16);
+        }
+    }
+}
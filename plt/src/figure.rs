@@ -1,9 +1,11 @@
 use crate::backend;
-use crate::layout::{FractionalArea, Layout};
+use crate::layout::{find_overlap, FractionalArea, Layout, SubplotArea};
 use crate::subplot::{
-    AxisType, Grid, Line, LineStyle, MarkerStyle, PlotType, Subplot, TickDirection, TickLabels, TickSpacing,
+    AxisScale, AxisType, Grid, LegendPosition, Line, LineStyle, Marker, MarkerStyle, Orientation, PlotType, ReferenceKind,
+    Subplot, TickDirection, TickLabels, TickSpacing,
 };
-use crate::{Color, FileFormat, PltError};
+use crate::ticks::{modifier_text, round_to, superscript, tick_modifiers, ticks_to_labels};
+use crate::{Bitmap, Color, Colormap, FileFormat, PltError};
 
 use std::collections::HashMap;
 use std::{f64, iter, marker, ops, path};
@@ -15,21 +17,35 @@ use std::{f64, iter, marker, ops, path};
 #[cfg(feature = "cairo")]
 pub struct Figure<'a, B: backend::Canvas = backend::CairoCanvas> {
     subplots: Vec<Subplot<'a>>,
-    subplot_areas: Vec<FractionalArea>,
+    subplot_areas: Vec<SubplotArea>,
     size: draw::Size,
     scaling: f32,
     dpi: u16,
+    lossless: bool,
+    quality: u8,
     face_color: Color,
+    margin: f64,
+    border: Option<(Color, u32)>,
+    antialias: bool,
+    seed: u64,
+    strict: bool,
     phantom: marker::PhantomData<B>,
 }
 #[cfg(not(feature = "cairo"))]
 pub struct Figure<'a, B: backend::Canvas> {
     subplots: Vec<Subplot<'a>>,
-    subplot_areas: Vec<draw::Area>,
+    subplot_areas: Vec<SubplotArea>,
     size: draw::Size,
     scaling: f32,
     dpi: u16,
+    lossless: bool,
+    quality: u8,
     face_color: Color,
+    margin: f64,
+    border: Option<(Color, u32)>,
+    antialias: bool,
+    seed: u64,
+    strict: bool,
     phantom: marker::PhantomData<B>,
 }
 impl<'a, B: backend::Canvas> Figure<'a, B> {
@@ -48,35 +64,132 @@ impl<'a, B: backend::Canvas> Figure<'a, B> {
             size: draw::Size { width, height },
             scaling,
             dpi: format.dpi,
+            lossless: format.lossless,
+            quality: format.quality,
             face_color: format.face_color,
+            margin: format.margin,
+            border: format.border,
+            antialias: format.antialias,
+            // fixed so output is reproducible unless a seed is chosen
+            seed: 0,
+            strict: false,
             phantom: marker::PhantomData,
         }
     }
 
     /// Adds subplots to the figure through a [`Layout`].
+    /// Subplot areas are inset by the figure's [`FigureFormat::margin`].
+    ///
+    /// Returns [`PltError::InvalidSubplotArea`] if an area is not valid.
+    /// Overlapping areas are allowed, while [`Figure::set_layout_checked`] rejects them.
     pub fn set_layout<'b, L: Layout<'a>>(&'b mut self, layout: L) -> Result<(), PltError> {
-        let (mut subplots, mut frac_areas): (Vec<Subplot>, Vec<FractionalArea>) = layout.subplots()
+        self.add_layout(layout, false)
+    }
+
+    /// Adds subplots to the figure through a [`Layout`], like [`Figure::set_layout`],
+    /// but also returns [`PltError::OverlappingSubplots`] if two subplot areas overlap,
+    /// unless the layout allows overlap with [`Layout::allows_overlap`], as [`InsetLayout`](crate::InsetLayout) does.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// /// Draws two subplots over the whole figure.
+    /// struct Stacked<'a>(Subplot<'a>, Subplot<'a>);
+    /// impl<'a> Layout<'a> for Stacked<'a> {
+    ///     fn subplots(self) -> Vec<(Subplot<'a>, FractionalArea)> {
+    ///         let area = FractionalArea::new(0.0, 1.0, 0.0, 1.0).unwrap();
+    ///
+    ///         vec![(self.0, area), (self.1, area)]
+    ///     }
+    /// }
+    ///
+    /// let stacked = || Stacked(Subplot::builder().build(), Subplot::builder().build());
+    ///
+    /// let mut fig = <Figure>::default();
+    /// fig.set_layout(stacked()).unwrap();
+    ///
+    /// let mut fig = <Figure>::default();
+    /// assert!(matches!(fig.set_layout_checked(stacked()), Err(PltError::OverlappingSubplots(..))));
+    /// ```
+    pub fn set_layout_checked<'b, L: Layout<'a>>(&'b mut self, layout: L) -> Result<(), PltError> {
+        self.add_layout(layout, true)
+    }
+
+    /// Adds subplots through a layout, checking for overlapping areas if `check_overlap` is set.
+    fn add_layout<L: Layout<'a>>(&mut self, layout: L, check_overlap: bool) -> Result<(), PltError> {
+        let check_overlap = check_overlap && !layout.allows_overlap();
+        let (mut subplots, frac_areas): (Vec<Subplot>, Vec<FractionalArea>) = layout.subplots()
             .into_iter()
+            .map(|(subplot, area)| (subplot, area.inset(self.margin)))
             .unzip();
 
         if let Some(area) = frac_areas.iter().find(|area| !area.valid()) {
             return Err(PltError::InvalidSubplotArea(*area));
         }
+        if check_overlap {
+            if let Some((area, other)) = find_overlap(&frac_areas) {
+                return Err(PltError::OverlappingSubplots(area, other));
+            }
+        }
 
         self.subplots.append(&mut subplots);
-        self.subplot_areas.append(&mut frac_areas);
+        self.subplot_areas.extend(frac_areas.into_iter().map(SubplotArea::Fractional));
+
+        Ok(())
+    }
+
+    /// Adds a subplot drawn in an exact area of the figure, in pixels from the bottom left corner.
+    /// Unlike areas from a [`Layout`], the area is not rounded or inset by the figure's margin,
+    /// so subplots can be aligned to a pixel grid.
+    ///
+    /// Returns [`PltError::InvalidPixelArea`] if the area is empty or extends past the figure.
+    pub fn add_subplot_at(&mut self, subplot: Subplot<'a>, area: draw::Area) -> Result<(), PltError> {
+        if area.xmin >= area.xmax || area.ymin >= area.ymax
+            || area.xmax > self.size.width || area.ymax > self.size.height
+        {
+            return Err(PltError::InvalidPixelArea(area));
+        }
+
+        self.subplots.push(subplot);
+        self.subplot_areas.push(SubplotArea::Pixel(area));
 
         Ok(())
     }
 
     /// Draw figure to provided backend.
+    ///
+    /// The figure is drawn to fit the size of the backend, without painting its face color,
+    /// so it composites over existing content, such as a larger document drawn with Cairo.
+    ///
+    /// ```
+    /// use plt::*;
+    /// use plt::backend::{cairo, CairoCanvas, ImageFormat, Size};
+    ///
+    /// let mut subplot = Subplot::builder().build();
+    /// subplot.plot([0.0, 1.0, 2.0], [0.0, 4.0, 1.0]).unwrap();
+    /// let mut fig = <Figure>::default();
+    /// fig.set_layout(SingleLayout::new(subplot)).unwrap();
+    ///
+    /// // draw into the lower right quarter of an existing surface
+    /// let surface = cairo::ImageSurface::create(cairo::Format::ARgb32, 800, 600).unwrap();
+    /// let context = cairo::Context::new(&surface).unwrap();
+    /// context.translate(400.0, 300.0);
+    ///
+    /// let size = Size { width: 400, height: 300 };
+    /// let mut canvas = CairoCanvas::from_context(&context, size, ImageFormat::Bitmap);
+    /// fig.draw_to_backend(&mut canvas).unwrap();
+    /// ```
     pub fn draw_to_backend(&mut self, backend: &mut B) -> Result<(), PltError> {
         let old_size = self.size;
         self.size = backend.size()?;
 
         for (subplot, subplot_area) in iter::zip(&self.subplots, &self.subplot_areas) {
             let subplot_area = subplot_area.to_area(self.size);
-            draw_subplot(backend, subplot, &subplot_area, self.scaling)?;
+            draw_subplot(backend, subplot, &subplot_area, self.scaling, self.seed, self.strict)?;
+        }
+
+        if let Some(border) = self.border {
+            draw_border(backend, self.size, border, self.scaling)?;
         }
 
         self.size = old_size;
@@ -91,35 +204,70 @@ impl<'a, B: backend::Canvas> Figure<'a, B> {
         filename: P,
     ) -> Result<(), PltError> {
         // create canvas to draw to
-        let image_format = match format {
-            FileFormat::Png => draw::ImageFormat::Bitmap,
-            FileFormat::Svg => draw::ImageFormat::Svg,
-            _ => draw::ImageFormat::Bitmap,
-        };
-        let mut canvas = B::new(draw::CanvasDescriptor {
-            size: self.size,
-            face_color: self.face_color,
-            image_format,
-        })?;
-
-        for (subplot, subplot_area) in iter::zip(&self.subplots, &self.subplot_areas) {
-            let subplot_area = subplot_area.to_area(self.size);
-            draw_subplot(&mut canvas, subplot, &subplot_area, self.scaling)?;
-        }
+        let mut canvas = self.draw_canvas(image_format(format))?;
 
         // save to file
         canvas.save_file(draw::SaveFileDescriptor {
             filename: filename.as_ref(),
             format,
             dpi: self.dpi,
+            lossless: self.lossless,
+            quality: self.quality,
         })?;
 
         Ok(())
     }
 
+    /// Draw figure to in-memory bytes, encoded as they would be in a file.
+    pub fn draw_bytes(&self, format: FileFormat) -> Result<Vec<u8>, PltError> {
+        let mut canvas = self.draw_canvas(image_format(format))?;
+
+        Ok(canvas.save_bytes(draw::SaveBytesDescriptor {
+            format,
+            dpi: self.dpi,
+            lossless: self.lossless,
+            quality: self.quality,
+        })?)
+    }
+
+    /// Draw figure to an in-memory [`Bitmap`].
+    ///
+    /// Pixel colors have straight alpha, not premultiplied by it, the same as in PNG files.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let draw_fill = |face_color| {
+    ///     let mut subplot = Subplot::builder().build();
+    ///     subplot.filler()
+    ///         .color(Color::new(1.0, 0.0, 0.0, 0.5))
+    ///         .fill_between([0.0, 1.0], [0.0, 0.0], [1.0, 1.0])
+    ///         .unwrap();
+    ///
+    ///     let mut fig = <Figure>::new(&FigureFormat { face_color, ..Default::default() });
+    ///     fig.set_layout(SingleLayout::new(subplot)).unwrap();
+    ///     let bitmap = fig.draw_bitmap().unwrap();
+    ///
+    ///     // the center of the figure, which is filled
+    ///     let (width, height) = (bitmap.size.width as usize, bitmap.size.height as usize);
+    ///     let center = 4 * (height / 2 * width + width / 2);
+    ///     bitmap.data[center..center + 4].to_vec()
+    /// };
+    ///
+    /// // half red over white is opaque pink
+    /// assert_eq!(draw_fill(Color::WHITE), [255, 127, 127, 255]);
+    /// // half red over nothing is red at half opacity, not darkened
+    /// assert_eq!(draw_fill(Color::TRANSPARENT), [255, 0, 0, 128]);
+    /// ```
+    pub fn draw_bitmap(&self) -> Result<Bitmap, PltError> {
+        let mut canvas = self.draw_canvas(draw::ImageFormat::Bitmap)?;
+
+        Ok(canvas.bitmap()?)
+    }
+
     /// Get reference to held subplots.
     #[deprecated]
-    pub fn subplots<'b>(&'b mut self) -> &mut Vec<Subplot<'a>>
+    pub fn subplots<'b>(&'b mut self) -> &'b mut Vec<Subplot<'a>>
     where
         'a: 'b,
     {
@@ -134,12 +282,49 @@ impl<'a, B: backend::Canvas> Figure<'a, B> {
         self.size = draw::Size { width, height };
     }
 
+    /// Sets the seed for randomized rendering, such as jitter from the `jitter` feature.
+    #[cfg_attr(feature = "jitter", doc = "See [`Plotter::jitter`](crate::Plotter::jitter).")]
+    /// By default, a fixed seed is used, so drawing the same figure always gives the same output.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    /// Sets whether drawing checks that all content fits in the figure.
+    /// In strict mode, drawing returns [`PltError::OutOfBounds`] if any text extends past the figure edges,
+    /// which is useful for catching clipped labels in tests.
+    /// Defaults to `false`.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
     /// Removes all subplots from figure.
     pub fn clear(&mut self) {
         self.subplots.clear();
         self.subplot_areas.clear();
     }
 }
+impl<'a, B: backend::Canvas> Figure<'a, B> {
+    /// Draw figure to a new canvas.
+    fn draw_canvas(&self, image_format: draw::ImageFormat) -> Result<B, PltError> {
+        let mut canvas = B::new(draw::CanvasDescriptor {
+            size: self.size,
+            face_color: self.face_color,
+            image_format,
+            antialias: self.antialias,
+        })?;
+
+        for (subplot, subplot_area) in iter::zip(&self.subplots, &self.subplot_areas) {
+            let subplot_area = subplot_area.to_area(self.size);
+            draw_subplot(&mut canvas, subplot, &subplot_area, self.scaling, self.seed, self.strict)?;
+        }
+
+        if let Some(border) = self.border {
+            draw_border(&mut canvas, self.size, border, self.scaling)?;
+        }
+
+        Ok(canvas)
+    }
+}
 impl<'a, B: backend::Canvas> Default for Figure<'a, B> {
     fn default() -> Self {
         Self::new(&FigureFormat::default())
@@ -152,16 +337,100 @@ pub struct FigureFormat {
     /// The size of the figure, in inches.
     pub size: FigSize,
     /// The dots (pixels) per inch of the figure.
+    ///
+    /// Text, lines, and the spacing between them all scale with the DPI,
+    /// so a figure has the same layout at any DPI, only at a different resolution.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// // the column of the y-axis line, and the width of the figure
+    /// let yaxis_column = |dpi| {
+    ///     let mut subplot = Subplot::builder().label(Axes::Y, "y").build();
+    ///     subplot.plot([0.0, 1.0], [0.0, 1.0]).unwrap();
+    ///
+    ///     let mut fig = <Figure>::new(&FigureFormat { dpi, ..Default::default() });
+    ///     fig.set_layout(SingleLayout::new(subplot)).unwrap();
+    ///     let bitmap = fig.draw_bitmap().unwrap();
+    ///
+    ///     // the first column that is mostly dark
+    ///     let (width, height) = (bitmap.size.width, bitmap.size.height);
+    ///     let dark = |x, y| bitmap.data[(4 * (y * width + x)) as usize] < 64;
+    ///     let column = (0..width)
+    ///         .find(|&x| (0..height).filter(|&y| dark(x, y)).count() as u32 > height / 2)
+    ///         .unwrap();
+    ///
+    ///     (column as f64, width as f64)
+    /// };
+    ///
+    /// // the same fraction of the figure width, give or take rounding to whole dots
+    /// let (column, width) = yaxis_column(100);
+    /// for dpi in [72, 150, 300] {
+    ///     let (scaled_column, scaled_width) = yaxis_column(dpi);
+    ///     assert!((scaled_column - column / width * scaled_width).abs() < 1.5);
+    /// }
+    /// ```
     pub dpi: u16,
+    /// Whether WebP files are saved losslessly, or as smaller lossy images compressed by [`Self::quality`].
+    /// JPEG files are always lossy.
+    pub lossless: bool,
+    /// The quality of saved files, from 0 to 100.
+    ///
+    /// For JPEG and lossy WebP files this sets how coarsely the image is compressed.
+    /// For lossless WebP files this sets the compression effort.
+    /// Defaults to 90, which keeps lines and text in plots sharp.
+    pub quality: u8,
     /// The background color of the figure.
+    ///
+    /// A transparent face color leaves the background transparent in bitmaps and PNG files.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let mut fig = <Figure>::new(&FigureFormat { face_color: Color::TRANSPARENT, ..Default::default() });
+    /// fig.set_layout(SingleLayout::new(Subplot::builder().build())).unwrap();
+    /// let bitmap = fig.draw_bitmap().unwrap();
+    ///
+    /// // the corner is outside of the subplot, so it only has the face color
+    /// assert_eq!(bitmap.data[..4], [0, 0, 0, 0]);
+    /// ```
     pub face_color: Color,
+    /// The fraction of the figure's width and height left empty around each edge,
+    /// before subplots start.
+    pub margin: f64,
+    /// The color and width of a border drawn around the edge of the figure, on top of subplots.
+    /// The width scales with the DPI, like other lines.
+    pub border: Option<(Color, u32)>,
+    /// Whether the edges of lines, shapes, and text are anti-aliased.
+    ///
+    /// Disabling anti-aliasing, along with pixel-perfect data, draws every edge with solid dots.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let mut subplot = Subplot::builder().label(Axes::X, "x").build();
+    /// subplot.plotter().line_color(Color::BLACK).plot([0.0, 1.0, 2.0], [0.0, 2.0, 1.0]).unwrap();
+    ///
+    /// let mut fig = <Figure>::new(&FigureFormat { antialias: false, ..Default::default() });
+    /// fig.set_layout(SingleLayout::new(subplot)).unwrap();
+    /// let bitmap = fig.draw_bitmap().unwrap();
+    ///
+    /// // every dot is either the white background or black
+    /// assert!(bitmap.data.chunks(4).all(|dot| dot[0] == 0 || dot[0] == 255));
+    /// ```
+    pub antialias: bool,
 }
 impl Default for FigureFormat {
     fn default() -> Self {
         Self {
             size: FigSize { width: 6.75, height: 5.00 },
             dpi: 100,
+            lossless: true,
+            quality: 90,
             face_color: Color::WHITE,
+            margin: 0.0,
+            border: None,
+            antialias: true,
         }
     }
 }
@@ -175,6 +444,7 @@ pub struct FigSize {
 
 // private
 
+#[allow(dead_code)]
 struct SubplotList<'a> {
     subplots: &'a mut Vec<Subplot<'a>>,
     rows: usize,
@@ -204,201 +474,586 @@ struct AxisFinalized {
     pub minor_grid: bool,
     pub limits: (f64, f64),
     pub visible: bool,
+    pub modifier_in_label: bool,
+    /// The counterclockwise rotation of tick labels, in degrees.
+    pub tick_label_rotation: f64,
+    pub scale: AxisScale,
 }
 
-fn sigdigit(num: f64) -> i32 {
-    let mut num = num.abs();
-    if num == 0.0 {
-        return i32::MIN;
+/// The number of evenly spaced ticks labeled on a colorbar.
+const COLORBAR_TICKS: usize = 5;
+/// The most colors sampled from a colormap to draw a colorbar.
+const COLORBAR_SLICES: u32 = 256;
+/// The length and width of annotation arrowheads, in dots at the default DPI.
+const ARROW_HEAD_SIZE: (u32, u32) = (12, 8);
+/// The gap between annotation text and the start of its arrow, in dots at the default DPI.
+const ARROW_GAP: u32 = 4;
+/// The opacity of reference bands without a color override.
+const REFERENCE_SPAN_ALPHA: f64 = 0.2;
+
+struct ColorbarFinalized {
+    pub colormap: Colormap,
+    pub tick_labels: Vec<String>,
+    pub modifier: String,
+    pub label_width: u32,
+}
+
+/// The kind of canvas needed to save a file format.
+fn image_format(format: FileFormat) -> draw::ImageFormat {
+    match format {
+        FileFormat::Svg => draw::ImageFormat::Svg,
+        FileFormat::Pdf => draw::ImageFormat::Pdf,
+        _ => draw::ImageFormat::Bitmap,
     }
+}
 
-    if num > 1.0 {
-        let mut ret = 0;
-        while num >= 10.0 {
-            num /= 10.0;
-            ret += 1;
-        }
-        ret
+/// Snaps a coordinate to a whole dot (pixel), always rounding halves up.
+/// Ticks, grid lines, and pixel perfect data all share this, so they land on the same dots as the spines.
+fn snap(coordinate: f64) -> f64 {
+    (coordinate + 0.5).floor()
+}
+
+/// Ticks on whole powers within the limits of a logarithmic axis,
+/// skipping powers evenly when there are too many to label.
+/// Linear axes have none.
+fn power_ticks(scale: AxisScale, limits: (f64, f64)) -> Vec<f64> {
+    const MAX_TICKS: usize = 10;
+
+    if scale == AxisScale::Linear {
+        return vec![];
+    }
+
+    let low = scale.transform(f64::min(limits.0, limits.1)).ceil() as i32;
+    let high = scale.transform(f64::max(limits.0, limits.1)).floor() as i32;
+    let npowers = (high - low + 1).max(0) as usize;
+
+    (low..=high)
+        .step_by(npowers.div_ceil(MAX_TICKS).max(1))
+        .map(|power| scale.inverse(power as f64))
+        .collect()
+}
+
+/// Ticks on 2 to 9 times each power of 10 within limits.
+fn decade_minor_ticks(limits: (f64, f64)) -> Vec<f64> {
+    let (low, high) = (f64::min(limits.0, limits.1), f64::max(limits.0, limits.1));
+
+    ((low.log10().floor() as i32)..=(high.log10().floor() as i32))
+        .flat_map(|power| (2..=9).map(move |n| n as f64 * f64::powi(10.0, power)))
+        .filter(|&tick| tick >= low && tick <= high)
+        .collect()
+}
+
+/// Labels ticks on whole powers with their exponents, such as "10³" or "e²".
+fn power_labels(scale: AxisScale, ticks: &[f64]) -> Vec<String> {
+    let base = match scale {
+        AxisScale::Ln => "e",
+        _ => "10",
+    };
+
+    ticks.iter()
+        .map(|&tick| format!("{}{}", base, superscript(scale.transform(tick).round() as i32)))
+        .collect()
+}
+
+/// Scales a length in dots at the default DPI to the DPI of a figure, rounding to whole dots.
+/// Nonzero lengths stay at least one dot long, so thin lines are still drawn at low DPIs.
+fn scale_length(length: u32, scaling: f32) -> u32 {
+    if length == 0 {
+        0
     } else {
-        let mut ret = 0;
-        while num < 1.0 {
-            num *= 10.0;
-            ret -= 1;
-        }
-        ret
+        ((length as f32 * scaling).round() as u32).max(1)
     }
 }
 
-fn decimals(mut num: f64, ndigits: u8) -> Vec<u8> {
-    let mut decimals = Vec::with_capacity(ndigits as usize);
-    for _ in 0..ndigits {
-        num -= num.floor();
-        num *= 10.0;
-        decimals.push(num.floor() as u8);
+/// Measures the size of text in a font of scaled size, used to lay out text.
+fn text_size_of<B: backend::Canvas>(
+    canvas: &mut B,
+    text: &str,
+    font: &draw::Font,
+    scaling: f32,
+) -> Result<draw::Size, PltError> {
+    let text_size = canvas.text_size(draw::TextDescriptor {
+        text: text.to_owned(),
+        font: draw::Font {
+            size: font.size / scaling,
+            ..font.clone()
+        },
+        ..Default::default()
+    })?;
+
+    Ok(draw::Size {
+        width: (text_size.width as f32 * scaling).round() as u32,
+        height: (text_size.height as f32 * scaling).round() as u32,
+    })
+}
+
+/// The most ticks placed at multiples of a base, guarding against bases far smaller than the limits.
+const MAX_MULTIPLES: i64 = 1000;
+
+/// Places ticks at each integer multiple of a base within limits.
+fn multiples_of(base: f64, limits: (f64, f64)) -> Result<Vec<f64>, PltError> {
+    if !(base.is_finite() && base > 0.0) {
+        return Err(PltError::BadTickPlacement(format!(
+            "tick base of {} is not a positive number", base,
+        )));
     }
 
-    decimals
+    let (min, max) = (limits.0.min(limits.1), limits.0.max(limits.1));
+    let (first, last) = ((min / base).ceil(), (max / base).floor());
+    // count in floats, as the multiples of a small base can be outside of the range of integers
+    if (last - first).is_nan() || last - first > MAX_MULTIPLES as f64 {
+        return Err(PltError::BadTickPlacement(format!(
+            "tick base of {} gives more than {} ticks within limits", base, MAX_MULTIPLES,
+        )));
+    }
+
+    Ok((first as i64..=last as i64).map(|k| k as f64 * base).collect())
 }
 
-fn round_to(num: f64, place: i32) -> f64 {
-    (num * f64::powi(10.0, place)).round() / f64::powi(10.0, place)
+/// Labels ticks as multiples of a base named by a symbol, such as "π/2" or "2π",
+/// as fractions with the smallest denominator up to 12 that fits.
+fn multiple_labels(ticks: &[f64], base: f64, symbol: &str) -> Result<Vec<String>, PltError> {
+    if !(base.is_finite() && base != 0.0) {
+        return Err(PltError::BadTickLabels(format!(
+            "tick label base of {} is not a nonzero number", base,
+        )));
+    }
+
+    let labels = ticks.iter()
+        .map(|tick| {
+            let ratio = tick / base;
+            let fraction = (1..=12_i64)
+                .map(|denominator| ((ratio * denominator as f64).round(), denominator))
+                .find(|(numerator, denominator)| (numerator / *denominator as f64 - ratio).abs() < 1e-9);
+
+            let (numerator, denominator) = if let Some((numerator, denominator)) = fraction {
+                (numerator as i64, denominator)
+            } else {
+                return format!("{}{}", round_to(ratio, 3), symbol);
+            };
+
+            let multiple = match numerator {
+                0 => return "0".to_owned(),
+                1 => symbol.to_owned(),
+                -1 => format!("-{}", symbol),
+                n => format!("{}{}", n, symbol),
+            };
+
+            if denominator == 1 {
+                multiple
+            } else {
+                format!("{}/{}", multiple, denominator)
+            }
+        })
+        .collect();
+
+    Ok(labels)
 }
 
-fn superscript(n: i32) -> String {
-    if n == 0 {
-        "⁰".to_owned()
-    } else if n == 1 {
-        "¹".to_owned()
-    } else if n == 2 {
-        "²".to_owned()
-    } else if n == 3 {
-        "³".to_owned()
-    } else if n == 4 {
-        "⁴".to_owned()
-    } else if n == 5 {
-        "⁵".to_owned()
-    } else if n == 6 {
-        "⁶".to_owned()
-    } else if n == 7 {
-        "⁷".to_owned()
-    } else if n == 8 {
-        "⁸".to_owned()
-    } else if n == 9 {
-        "⁹".to_owned()
-    } else if n >= 10 {
-        superscript(n / 10) + &superscript(n % 10)
-    } else if n < 0 {
-        "⁻".to_owned() + &superscript(n.abs())
-    } else {
-        "".to_owned()
+/// A line with its color and scaled dimensions resolved, as drawn for a plot and its legend entry.
+struct ResolvedLine {
+    color: Color,
+    width: u32,
+    dashes: Vec<f64>,
+}
+impl ResolvedLine {
+    fn draw<B: backend::Canvas>(
+        &self,
+        canvas: &mut B,
+        points: Vec<draw::Point>,
+        clip_area: draw::Area,
+    ) -> Result<(), PltError> {
+        canvas.draw_curve(draw::CurveDescriptor {
+            points,
+            line_color: self.color,
+            line_width: self.width,
+            dashes: self.dashes.as_slice(),
+            clip_area: Some(clip_area),
+        })?;
+
+        Ok(())
     }
 }
 
-fn tick_modifiers(ticks: &[f64]) -> Result<(f64, i32, usize), PltError> {
-    // make sure there are no NaNs
-    if ticks.iter().any(|&tick| tick.is_nan()) {
-        return Err(PltError::BadTickPlacement("tick is NaN".to_owned()));
+/// A marker with its colors and scaled dimensions resolved, as drawn for a plot and its legend entry.
+struct ResolvedMarker<'a> {
+    style: &'a MarkerStyle,
+    size: u32,
+    fill_color: Color,
+    line: ResolvedLine,
+}
+impl<'a> ResolvedMarker<'a> {
+    /// Resolves a marker with a color, which fills it, and which outlines also use unless overridden.
+    /// Hollow markers are always outlined, and are not filled.
+    fn new(marker: &'a Marker, color: Color, scaling: f32) -> Self {
+        let outline = if marker.outline || !marker.fill {
+            marker.outline_format.clone()
+        } else {
+            Line {
+                style: LineStyle::Solid,
+                width: Line::default().width,
+                color_override: Some(Color::TRANSPARENT),
+            }
+        };
+
+        Self {
+            style: &marker.style,
+            size: scale_length(marker.size, scaling),
+            fill_color: if marker.fill { color } else { Color::TRANSPARENT },
+            line: ResolvedLine {
+                color: outline.color_override.unwrap_or(color),
+                width: scale_length(outline.width, scaling),
+                dashes: line_dashes(&outline.style, scaling),
+            },
+        }
+    }
+
+    fn draw<B: backend::Canvas>(
+        &self,
+        canvas: &mut B,
+        point: draw::Point,
+        clip_area: draw::Area,
+    ) -> Result<(), PltError> {
+        let shape = match self.style {
+            MarkerStyle::Circle => draw::Shape::Circle { r: self.size },
+            MarkerStyle::Square => draw::Shape::Square { l: self.size },
+            // spans the diameter of a circle of the same size
+            MarkerStyle::TriangleUp => draw::Shape::TriangleUp { l: 2 * self.size },
+            MarkerStyle::TriangleDown => draw::Shape::TriangleDown { l: 2 * self.size },
+            MarkerStyle::Diamond => draw::Shape::Diamond { l: 2 * self.size },
+            MarkerStyle::Plus => draw::Shape::Plus { l: 2 * self.size, thickness: self.size.div_ceil(2) },
+            MarkerStyle::Cross => draw::Shape::Cross { l: 2 * self.size, thickness: self.size.div_ceil(2) },
+            MarkerStyle::Custom(outline) => {
+                let outline = outline.iter()
+                    .map(|offset| draw::Point {
+                        x: point.x + offset.x * self.size as f64,
+                        y: point.y + offset.y * self.size as f64,
+                    })
+                    .collect::<Vec<_>>();
+
+                canvas.fill_region(draw::FillDescriptor {
+                    points: outline.clone(),
+                    fill_color: self.fill_color,
+                    clip_area: Some(clip_area),
+                })?;
+                // overlap the first segment, so that every corner is joined
+                return self.line.draw(
+                    canvas,
+                    outline.iter().chain(outline.iter().take(2)).copied().collect(),
+                    clip_area,
+                );
+            },
+        };
+
+        canvas.draw_shape(draw::ShapeDescriptor {
+            point,
+            shape,
+            fill_color: self.fill_color,
+            line_color: self.line.color,
+            line_width: self.line.width,
+            line_dashes: self.line.dashes.as_slice(),
+            clip_area: Some(clip_area),
+        })?;
+
+        Ok(())
     }
+}
+
+/// The colors of a series, with the color of each part that is drawn resolved.
+struct SeriesColors {
+    line: Option<Color>,
+    marker: Option<Color>,
+    errorbars: Option<Color>,
+}
+
+/// The colors of each data set, indexed the same as the data of its plot type in a subplot.
+struct DataColors {
+    series: Vec<SeriesColors>,
+    fills: Vec<Color>,
+    bars: Vec<Color>,
+    /// The line and marker colors of each stem plot.
+    stems: Vec<(Color, Color)>,
+}
+impl DataColors {
+    /// Resolves colors in the order data was added, so each part without a color override
+    /// takes the next color of the color cycle, regardless of z-order.
+    fn new(subplot: &Subplot) -> Self {
+        let format = &subplot.format;
+
+        // if there is a color cycle, default to those colors, otherwise default to black for series
+        let mut color_index = 0;
+        let mut default_color = || {
+            color_index += 1;
+            format.cycle_color(color_index - 1)
+        };
 
-    // return empty labels for empty ticks
-    if ticks.is_empty() {
-        return Ok((0.0, 0, 0));
+        // if there is a color cycle, default to those colors, otherwise default to red for fill
+        let mut fill_color_index = 0;
+        let mut default_fill_color = || {
+            fill_color_index += 1;
+            if format.color_cycle.is_empty() {
+                format.default_fill_color
+            } else {
+                let color = format.cycle_color(fill_color_index - 1);
+                Color::new(color.r, color.g, color.b, format.fill_alpha)
+            }
+        };
+
+        let mut colors = Self { series: vec![], fills: vec![], bars: vec![], stems: vec![] };
+        let mut plot_infos = subplot.plot_infos.iter();
+        let mut fill_infos = subplot.fill_infos.iter();
+        let mut bar_infos = subplot.bar_infos.iter();
+        let mut stem_infos = subplot.stem_infos.iter();
+
+        for plot_type in &subplot.plot_order {
+            match plot_type {
+                PlotType::Series => colors.series.extend(plot_infos.next().map(|info| {
+                    let line = info.line.as_ref()
+                        .map(|line| line.color_override.unwrap_or_else(&mut default_color));
+                    let marker = info.marker.as_ref()
+                        .map(|marker| marker.effective_color_override().unwrap_or_else(&mut default_color));
+                    // error bars match the line or markers, if either is drawn
+                    let errorbars = info.errorbars.as_ref().map(|errorbars| {
+                        errorbars.color_override.or(line).or(marker).unwrap_or_else(&mut default_color)
+                    });
+
+                    SeriesColors { line, marker, errorbars }
+                })),
+                PlotType::Fill => colors.fills.extend(fill_infos.next().map(|info| {
+                    info.color_override.unwrap_or_else(&mut default_fill_color)
+                })),
+                PlotType::Bar => colors.bars.extend(bar_infos.next().map(|info| {
+                    info.color_override.unwrap_or_else(&mut default_color)
+                })),
+                // the line and marker share a color, unless either is overridden
+                PlotType::Stem => colors.stems.extend(stem_infos.next().map(|info| {
+                    let (line_override, marker_override) = (
+                        info.line.color_override,
+                        info.marker.effective_color_override(),
+                    );
+                    let color = line_override.or(marker_override).unwrap_or_else(&mut default_color);
+
+                    (line_override.unwrap_or(color), marker_override.unwrap_or(color))
+                })),
+                PlotType::Heatmap | PlotType::Reference => {},
+            }
+        }
+
+        colors
     }
+}
 
-    // find the highest most significant digit location
-    let highest_nonzero_tick = ticks.iter()
-        .rev()
-        .cloned()
-        .find(|&n| n != 0.0);
-    let highest_nonzero_tick = if let Some(tick) = highest_nonzero_tick {
-        tick
-    } else {
-        return Ok((0.0, 0, 0));
-    };
-    let mut max_multiplier = sigdigit(highest_nonzero_tick);
-
-    // get differences between ticks
-    let difs = ticks
-        .windows(2)
-        .map(|window| window[1] - window[0])
-        .collect::<Vec<_>>();
-    // find the largest difference between any two consecutive ticks
-    let max_dif = *difs.iter()
-        .reduce(|max, dif| if dif > max { dif } else { max })
-        .unwrap();
-    // find the highest most significant digit of the max tick difference
-    let dif_multiplier = if max_dif != 0.0 {
-        sigdigit(max_dif)
-    } else {
-        max_multiplier
-    };
+/// The sample drawn next to a label in a legend.
+enum LegendSwatch<'a> {
+    Series { line: Option<ResolvedLine>, marker: Option<ResolvedMarker<'a>> },
+    Fill { color: Color, edge: Option<ResolvedLine> },
+}
 
-    // if multiplier of max dif is less than max_multiplier - 3, use offset
-    let offset = if dif_multiplier < max_multiplier - 3 {
-        ticks[0]
-    } else {
-        0.0
+/// The horizontal and vertical distances to inset each side of a plot area by,
+/// so the x-axis and y-axis have the same number of dots per unit of data.
+/// Returns `None` if either axis has no extent.
+fn aspect_inset(plot_area: draw::Area, xaxis: &AxisFinalized, yaxis: &AxisFinalized) -> Option<(u32, u32)> {
+    let extent = |axis: &AxisFinalized| {
+        (axis.scale.transform(axis.limits.1) - axis.scale.transform(axis.limits.0)).abs()
     };
+    let (xextent, yextent) = (extent(xaxis), extent(yaxis));
+    if !(xextent > 0.0 && xextent.is_finite() && yextent > 0.0 && yextent.is_finite()) {
+        return None;
+    }
 
-    // get true multiplier
-    max_multiplier = sigdigit(round_to(
-        highest_nonzero_tick - offset,
-        3 - dif_multiplier,
-    ));
-    let multiplier = if !(-2..=3).contains(&max_multiplier) {
-        max_multiplier
-    } else {
-        0
-    };
+    let (width, height) = (plot_area.xsize() as f64, plot_area.ysize() as f64);
+    let dots_per_unit = f64::min(width / xextent, height / yextent);
 
-    // get precision
-    let max_precision = if multiplier != 0 || max_multiplier < 0 {
-        3
-    } else {
-        3 - max_multiplier
+    Some((
+        ((width - dots_per_unit * xextent) / 2.0).round() as u32,
+        ((height - dots_per_unit * yextent) / 2.0).round() as u32,
+    ))
+}
+
+/// The dash pattern of a line style, scaled for the figure.
+fn line_dashes(style: &LineStyle, scaling: f32) -> Vec<f64> {
+    match style {
+        LineStyle::Solid => vec![],
+        LineStyle::Dashed => vec![
+            (10.0 * scaling).into(),
+            (10.0 * scaling).into(),
+            (10.0 * scaling).into(),
+            (10.0 * scaling).into(),
+        ],
+        LineStyle::ShortDashed => vec![
+            (4.0 * scaling).into(),
+            (4.0 * scaling).into(),
+            (4.0 * scaling).into(),
+            (4.0 * scaling).into(),
+        ],
+        LineStyle::Dotted => vec![
+            (2.0 * scaling).into(),
+            (3.0 * scaling).into(),
+        ],
+        LineStyle::DashDot => vec![
+            (10.0 * scaling).into(),
+            (4.0 * scaling).into(),
+            (2.0 * scaling).into(),
+            (4.0 * scaling).into(),
+        ],
+        LineStyle::Custom(dashes) => dashes.iter().map(|&dash| dash * scaling as f64).collect(),
+    }
+}
+
+/// The lower left and upper right corners of the box around drawn text, accounting for its rotation.
+fn text_bounds<B: backend::Canvas>(
+    canvas: &mut B,
+    desc: &draw::TextDescriptor,
+) -> Result<(draw::Point, draw::Point), PltError> {
+    let text_size = canvas.text_size(desc.clone())?;
+
+    // extent of the rotated text
+    let (width, height) = (text_size.width as f64, text_size.height as f64);
+    let (sin, cos) = desc.rotation.sin_cos();
+    let (width, height) = (
+        (width * cos).abs() + (height * sin).abs(),
+        (width * sin).abs() + (height * cos).abs(),
+    );
+
+    // offset of the lower left corner from the position
+    let (x, y) = (desc.position.x, desc.position.y);
+    let xmin = match desc.alignment {
+        draw::Alignment::Left | draw::Alignment::TopLeft | draw::Alignment::BottomLeft => x,
+        draw::Alignment::Right | draw::Alignment::TopRight | draw::Alignment::BottomRight => {
+            x - width
+        },
+        draw::Alignment::Center | draw::Alignment::Top | draw::Alignment::Bottom => {
+            x - width / 2.0
+        },
     };
-    let shifted_ticks = if multiplier != 0 {
-        ticks.iter()
-            .map(|&tick| {
-                let rounded = (tick * f64::powi(10.0, 3 - multiplier)).round();
-                rounded * f64::powi(10.0, -3)
-            })
-            .collect::<Vec<_>>()
-    } else {
-        ticks.to_vec()
+    let ymin = match desc.alignment {
+        draw::Alignment::Bottom | draw::Alignment::BottomLeft | draw::Alignment::BottomRight => y,
+        draw::Alignment::Top | draw::Alignment::TopLeft | draw::Alignment::TopRight => y - height,
+        draw::Alignment::Center | draw::Alignment::Left | draw::Alignment::Right => {
+            y - height / 2.0
+        },
     };
-    let precision = shifted_ticks.iter()
-        .map(|&tick| {
-            decimals(tick, max_precision as u8)
-                .iter()
-                .rposition(|&digit| digit != 0)
-                .map(|prec| prec + 1)
-                .unwrap_or(0)
-        })
-        .max()
-        .unwrap();
 
-    Ok((offset, multiplier, precision))
+    Ok((draw::Point { x: xmin, y: ymin }, draw::Point { x: xmin + width, y: ymin + height }))
 }
 
-fn ticks_to_labels(ticks: &[f64], modifiers: (f64, i32, usize)) -> Result<Vec<String>, PltError> {
-    // make sure there are no NaNs
-    if ticks.iter().any(|&tick| tick.is_nan()) {
-        return Err(PltError::BadTickPlacement("tick is NaN".to_owned()));
-    }
+/// Draws text, first checking that it fits in the canvas in strict mode.
+fn draw_text<B: backend::Canvas>(
+    canvas: &mut B,
+    strict: bool,
+    desc: draw::TextDescriptor,
+) -> Result<(), PltError> {
+    if strict && !desc.text.is_empty() {
+        let canvas_size = canvas.size()?;
+        let (lower, upper) = text_bounds(canvas, &desc)?;
+
+        let edge = if lower.x < 0.0 {
+            Some("left")
+        } else if upper.x > canvas_size.width as f64 {
+            Some("right")
+        } else if lower.y < 0.0 {
+            Some("bottom")
+        } else if upper.y > canvas_size.height as f64 {
+            Some("top")
+        } else {
+            None
+        };
 
-    // return empty labels for empty ticks
-    if ticks.is_empty() {
-        return Ok(vec![]);
+        if let Some(edge) = edge {
+            return Err(PltError::OutOfBounds(
+                format!("text `{}` extends past the {} edge of the figure", desc.text, edge)
+            ));
+        }
     }
 
-    let (offset, multiplier, precision) = modifiers;
+    canvas.draw_text(desc)?;
 
-    // sort ticks
-    let mut ticks = ticks.to_vec();
-    ticks.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    Ok(())
+}
 
-    for tick in ticks.iter_mut() {
-        *tick = round_to(*tick - offset, 4 - multiplier);
+/// Draws an arrow from one point to another, as a line ending in a filled triangular head at the tip.
+/// Arrows shorter than their head are drawn as just the head.
+fn draw_arrow<B: backend::Canvas>(
+    canvas: &mut B,
+    from: draw::Point,
+    to: draw::Point,
+    width: u32,
+    head_size: (f64, f64),
+    color: Color,
+    clip_area: draw::Area,
+) -> Result<(), PltError> {
+    let (head_length, head_width) = head_size;
+    let (dx, dy) = (to.x - from.x, to.y - from.y);
+    let length = dx.hypot(dy);
+    if length == 0.0 {
+        return Ok(());
     }
 
-    // shift numbers if necessary
-    let shifted_ticks = if multiplier != 0 {
-        ticks.iter()
-            .map(|&tick| {
-                let rounded = (tick * f64::powi(10.0, 3 - multiplier)).round();
-                rounded * f64::powi(10.0, -3)
-            })
-            .collect::<Vec<_>>()
-    } else {
-        ticks.to_vec()
+    // unit vectors along and across the arrow
+    let (ux, uy) = (dx / length, dy / length);
+    let (nx, ny) = (-uy, ux);
+
+    let base = draw::Point {
+        x: to.x - ux * head_length.min(length),
+        y: to.y - uy * head_length.min(length),
     };
 
-    let labels = shifted_ticks.iter()
-        .map(|tick| format!("{0:.1$}", tick, precision))
-        .collect::<Vec<_>>();
+    if length > head_length {
+        canvas.draw_line(draw::LineDescriptor {
+            line: draw::Line { p1: from, p2: base },
+            line_width: width,
+            line_color: color,
+            clip_area: Some(clip_area),
+            ..Default::default()
+        })?;
+    }
 
-    Ok(labels)
+    canvas.fill_region(draw::FillDescriptor {
+        points: vec![
+            to,
+            draw::Point { x: base.x + nx * head_width / 2.0, y: base.y + ny * head_width / 2.0 },
+            draw::Point { x: base.x - nx * head_width / 2.0, y: base.y - ny * head_width / 2.0 },
+        ],
+        fill_color: color,
+        clip_area: Some(clip_area),
+    })?;
+
+    Ok(())
+}
+
+/// Draws a border just inside the edges of a figure.
+fn draw_border<B: backend::Canvas>(
+    canvas: &mut B,
+    size: draw::Size,
+    (color, width): (Color, u32),
+    scaling: f32,
+) -> Result<(), PltError> {
+    let line_width = scale_length(width, scaling);
+    if line_width == 0 {
+        return Ok(());
+    }
+
+    // center the line half its width from the edges, so all of it is inside the figure
+    let inset = line_width as f64 / 2.0;
+    let (xmin, xmax) = (inset, size.width as f64 - inset);
+    let (ymin, ymax) = (inset, size.height as f64 - inset);
+    let corners = [
+        draw::Point { x: xmin, y: ymin },
+        draw::Point { x: xmax, y: ymin },
+        draw::Point { x: xmax, y: ymax },
+        draw::Point { x: xmin, y: ymax },
+    ];
+
+    // overlap the first segment, so that every corner is joined
+    canvas.draw_curve(draw::CurveDescriptor {
+        points: corners.iter().chain(corners.iter().take(2)).copied().collect(),
+        line_color: color,
+        line_width,
+        dashes: &[],
+        clip_area: None,
+    })?;
+
+    Ok(())
 }
 
 fn draw_subplot<B: backend::Canvas>(
@@ -406,34 +1061,77 @@ fn draw_subplot<B: backend::Canvas>(
     subplot: &Subplot,
     subplot_area: &draw::Area,
     scaling: f32,
+    seed: u64,
+    strict: bool,
 ) -> Result<(), PltError> {
+    // custom dash patterns must have lengths to draw
+    let drawable = |dashes: &[f64]| {
+        dashes.iter().all(|&dash| dash.is_finite() && dash >= 0.0)
+            && (dashes.is_empty() || dashes.iter().any(|&dash| dash > 0.0))
+    };
+    let invalid_dashes = subplot.plot_infos.iter()
+        .flat_map(|info| [info.line.as_ref(), info.marker.as_ref().map(|marker| &marker.outline_format)])
+        .chain(subplot.fill_infos.iter().map(|info| info.edge.as_ref()))
+        .chain(subplot.stem_infos.iter()
+            .flat_map(|info| [Some(&info.line), Some(&info.marker.outline_format)]))
+        .chain(subplot.reference_infos.iter().map(|info| Some(&info.line)))
+        .flatten()
+        .map(|line| &line.style)
+        .chain(subplot.format.minor_grid_style.as_ref())
+        .find_map(|style| match style {
+            LineStyle::Custom(dashes) if !drawable(dashes) => Some(dashes),
+            _ => None,
+        });
+    if let Some(dashes) = invalid_dashes {
+        return Err(PltError::InvalidData(format!(
+            "dash pattern {:?} must have finite, non-negative lengths that are not all zero",
+            dashes,
+        )));
+    }
+
     // set formatting parameters
 
     // line formatting
-    let line_width = subplot.format.line_width * scaling.round() as u32;
+    let line_width = scale_length(subplot.format.line_width, scaling);
     let line_color = subplot.format.line_color;
 
     let grid_color = subplot.format.grid_color;
+    let minor_grid_color = subplot.format.minor_grid_color.unwrap_or(grid_color);
+    let minor_grid_width = subplot.format.minor_grid_width.map_or(line_width, |width| scale_length(width, scaling));
+    let minor_grid_dashes = subplot.format.minor_grid_style.as_ref()
+        .map_or(vec![], |style| line_dashes(style, scaling));
 
     // text formatting
     let font_name = subplot.format.font_name.clone();
     let font_size = subplot.format.font_size * scaling;
+    let tick_label_font_size = subplot.format.tick_label_font_size
+        .unwrap_or(subplot.format.font_size) * scaling;
+    let axis_label_font_size = subplot.format.axis_label_font_size
+        .unwrap_or(subplot.format.font_size) * scaling;
+    let title_font_size = subplot.format.title_font_size
+        .unwrap_or(subplot.format.font_size) * scaling;
+    let font = draw::Font { name: font_name.clone(), size: font_size, ..Default::default() };
+    let tick_label_font = draw::Font { size: tick_label_font_size, ..font.clone() };
+    let axis_label_font = subplot.format.label_font.as_ref().map_or_else(
+        || draw::Font { size: axis_label_font_size, ..font.clone() },
+        |label_font| draw::Font { size: label_font.size * scaling, ..label_font.clone() },
+    );
+    let title_font = subplot.format.title_font.as_ref().map_or_else(
+        || draw::Font { size: title_font_size, ..font.clone() },
+        |title_font| draw::Font { size: title_font.size * scaling, ..title_font.clone() },
+    );
     let font_color = subplot.format.text_color;
 
-    // colors
-    let default_marker_color = subplot.format.default_marker_color;
-    let default_fill_color = subplot.format.default_fill_color;
-
     // major tick formatting
     let inner_major_tick_length = match subplot.format.tick_direction {
         TickDirection::Inner | TickDirection::Both => {
-            subplot.format.tick_length * scaling.round() as u32
+            scale_length(subplot.format.tick_length, scaling)
         },
         _ => 0,
     };
     let outer_major_tick_length = match subplot.format.tick_direction {
         TickDirection::Outer | TickDirection::Both => {
-            subplot.format.tick_length * scaling.round() as u32
+            scale_length(subplot.format.tick_length, scaling)
         },
         _ => 0,
     };
@@ -441,9 +1139,9 @@ fn draw_subplot<B: backend::Canvas>(
     let inner_minor_tick_length = match subplot.format.tick_direction {
         TickDirection::Inner | TickDirection::Both => {
             if let Some(length) = subplot.format.override_minor_tick_length {
-                length * scaling.round() as u32
+                scale_length(length, scaling)
             } else {
-                subplot.format.tick_length * scaling.round() as u32 / 2
+                scale_length(subplot.format.tick_length, scaling) / 2
             }
         },
         _ => 0,
@@ -451,28 +1149,19 @@ fn draw_subplot<B: backend::Canvas>(
     let outer_minor_tick_length = match subplot.format.tick_direction {
         TickDirection::Outer | TickDirection::Both => {
             if let Some(length) = subplot.format.override_minor_tick_length {
-                length * scaling.round() as u32
+                scale_length(length, scaling)
             } else {
-                subplot.format.tick_length * scaling.round() as u32 / 2
+                scale_length(subplot.format.tick_length, scaling) / 2
             }
         },
         _ => 0,
     };
 
-    // layout depends on the font size
-    let letter_size = canvas.text_size(draw::TextDescriptor {
-        text: format!("{}", 0),
-        font: draw::Font {
-            name: font_name.clone(),
-            size: font_size / scaling,
-            ..Default::default()
-        },
-        ..Default::default()
-    })?;
-    let letter_size = draw::Size {
-        width: (letter_size.width as f32 * scaling) as u32,
-        height: (letter_size.height as f32 * scaling) as u32,
-    };
+    // layout depends on the font sizes
+    let letter_size = text_size_of(canvas, "0", &font, scaling)?;
+    let tick_label_letter_size = text_size_of(canvas, "0", &tick_label_font, scaling)?;
+    let axis_label_letter_size = text_size_of(canvas, "0", &axis_label_font, scaling)?;
+    let title_letter_size = text_size_of(canvas, "0", &title_font, scaling)?;
 
     // the pixel buffer sizes for fitting text on the figure sides
     let buffer_offset = ((letter_size.height as f64) * 0.6) as u32;
@@ -517,9 +1206,10 @@ fn draw_subplot<B: backend::Canvas>(
             AxisType::SecondaryX => &subplot.secondary_xaxis,
         };
 
-        // get span and limits for each axis, if None, use values from opposite side
-        let (span, limits) = if let (Some(span), Some(limits)) = (axis.span, axis.limits) {
-            (span, limits)
+        // get span, limits, and scale for each axis, which always has both if it has manual limits or data,
+        // so only an axis with neither takes the values of the opposite side
+        let (span, limits, scale) = if let (Some(span), Some(limits)) = (axis.span, axis.limits) {
+            (span, limits, axis.scale)
         } else {
             // use opposite side, if it has a value, otherwise default to (-1.0, 1.0)
             let opposite_axis = match placement {
@@ -538,58 +1228,145 @@ fn draw_subplot<B: backend::Canvas>(
             };
 
             if let (Some(span), Some(limits)) = (opposite_axis.span, opposite_axis.limits) {
-                (span, limits)
+                (span, limits, opposite_axis.scale)
+            } else if axis.scale != AxisScale::Linear {
+                ((1.0, 10.0), (1.0, 10.0), axis.scale)
             } else {
-                ((-1.0, 1.0), (-1.0, 1.0))
+                ((-1.0, 1.0), (-1.0, 1.0), axis.scale)
             }
         };
+        // inverted axes reverse their limits, like manual limits with min greater than max
+        let (span, limits) = if axis.inverted {
+            ((span.1, span.0), (limits.1, limits.0))
+        } else {
+            (span, limits)
+        };
+
+        // logarithmic axes can only show positive values
+        if scale != AxisScale::Linear {
+            let nonpositive_data = subplot.plot_infos.iter()
+                .flat_map(|info| [(info.xaxis, info.data.xmin()), (info.yaxis, info.data.ymin())])
+                .chain(subplot.fill_infos.iter()
+                    .flat_map(|info| [(info.xaxis, info.data.xmin()), (info.yaxis, info.data.ymin())]))
+                .chain(subplot.bar_infos.iter()
+                    .flat_map(|info| {
+                        let ((xmin, _), (ymin, _)) = info.extents();
+                        [(info.xaxis, xmin), (info.yaxis, ymin)]
+                    }))
+                .chain(subplot.stem_infos.iter()
+                    .flat_map(|info| {
+                        let ((xmin, _), (ymin, _)) = info.extents();
+                        [(info.xaxis, xmin), (info.yaxis, ymin)]
+                    }))
+                .chain(subplot.heatmap_infos.iter()
+                    .flat_map(|info| {
+                        let ((xmin, _), (ymin, _)) = info.extents();
+                        [(info.xaxis, xmin), (info.yaxis, ymin)]
+                    }))
+                .any(|(data_axis, min)| data_axis == placement && min <= 0.0);
+
+            if nonpositive_data {
+                return Err(PltError::InvalidData(format!(
+                    "{} is logarithmic, but has data that is not positive",
+                    placement.name(),
+                )));
+            } else if limits.0 <= 0.0 || limits.1 <= 0.0 {
+                return Err(PltError::InvalidData(format!(
+                    "{} is logarithmic, but has limits of {} to {} that are not positive",
+                    placement.name(), limits.0, limits.1,
+                )));
+            }
+        }
+
+        // equal or non-finite limits cannot be mapped to the plot area
+        if limits.0 == limits.1 || !limits.0.is_finite() || !limits.1.is_finite() {
+            return Err(PltError::InvalidData(format!(
+                "{} limits of {} to {} do not span a range of values",
+                placement.name(), limits.0, limits.1,
+            )));
+        }
 
         let is_primary = subplot.plot_infos.iter()
             .any(|info| info.xaxis == placement || info.yaxis == placement)
             | subplot.fill_infos.iter()
+            .any(|info| info.xaxis == placement || info.yaxis == placement)
+            | subplot.bar_infos.iter()
+            .any(|info| info.xaxis == placement || info.yaxis == placement)
+            | subplot.stem_infos.iter()
+            .any(|info| info.xaxis == placement || info.yaxis == placement)
+            | subplot.heatmap_infos.iter()
             .any(|info| info.xaxis == placement || info.yaxis == placement);
 
+        // locators place ticks within the limits
+        let locate = |locator: &dyn Fn(f64, f64) -> Vec<f64>| {
+            let (min, max) = (limits.0.min(limits.1), limits.0.max(limits.1));
+
+            locator(min, max).into_iter()
+                .filter(|tick| (min..=max).contains(tick))
+                .collect::<Vec<_>>()
+        };
+
         // get major tick marks
         let major_ticks = if let TickSpacing::Manual(ticks) = &axis.major_tick_marks {
             ticks.clone()
+        } else if let TickSpacing::Locator(locator) = &axis.major_tick_marks {
+            locate(locator.as_ref())
+        } else if let TickSpacing::MultiplesOf(base) = &axis.major_tick_marks {
+            multiples_of(*base, limits)?
         } else {
             let nticks = match &axis.major_tick_marks {
                 TickSpacing::Count(n) => *n,
                 TickSpacing::On => 5,
-                TickSpacing::Auto => {
-                    if is_primary {
-                        5
-                    } else {
-                        0
-                    }
-                },
+                TickSpacing::Auto if is_primary => 5,
                 TickSpacing::None => 0,
                 _ => 0,
             };
 
-            (0..nticks)
-                .map(|n| span.0 + (span.1 - span.0) * (n as f64 / (nticks - 1) as f64))
-                .collect::<Vec<_>>()
+            // logarithmic axes have ticks on each power, when there is more than one
+            let powers = power_ticks(scale, limits);
+            if nticks > 0 && powers.len() > 1 {
+                powers
+            } else {
+                (0..nticks)
+                    .map(|n| span.0 + (span.1 - span.0) * (n as f64 / (nticks - 1) as f64))
+                    .collect::<Vec<_>>()
+            }
+        };
+        let on_powers = !matches!(
+            axis.major_tick_marks,
+            TickSpacing::Manual(_) | TickSpacing::Locator(_) | TickSpacing::MultiplesOf(_),
+        )
+            && !major_ticks.is_empty()
+            && major_ticks == power_ticks(scale, limits);
+        // located major ticks are not spread over the span, so minor ticks divide their own spacing
+        let major_span = match &axis.major_tick_marks {
+            TickSpacing::Locator(_) | TickSpacing::MultiplesOf(_) => (major_ticks.len() > 1)
+                .then(|| (major_ticks[0], major_ticks[major_ticks.len() - 1])),
+            _ => (!major_ticks.is_empty()).then_some(span),
         };
         // get minor tick marks
         let minor_ticks = if let TickSpacing::Manual(ticks) = &axis.minor_tick_marks {
             ticks.clone()
+        } else if let TickSpacing::Locator(locator) = &axis.minor_tick_marks {
+            locate(locator.as_ref())
+        } else if let TickSpacing::MultiplesOf(base) = &axis.minor_tick_marks {
+            multiples_of(*base, limits)?
         } else {
             let nticks_per_major = match &axis.minor_tick_marks {
                 TickSpacing::Count(n) => *n,
                 TickSpacing::On => 4,
-                TickSpacing::Auto => {
-                    if is_primary {
-                        4
-                    } else {
-                        0
-                    }
-                },
+                TickSpacing::Auto if is_primary => 4,
                 TickSpacing::None => 0,
                 _ => 0,
             };
 
-            if !major_ticks.is_empty() {
+            if on_powers {
+                if scale == AxisScale::Log10 && nticks_per_major > 0 {
+                    decade_minor_ticks(limits)
+                } else {
+                    vec![]
+                }
+            } else if let Some(span) = major_span {
                 let major_tick_delta = (span.1 - span.0) / (major_ticks.len() - 1) as f64;
                 let minor_tick_delta = major_tick_delta / (nticks_per_major + 1) as f64;
 
@@ -606,27 +1383,33 @@ fn draw_subplot<B: backend::Canvas>(
 
 
         };
-        // remove overlap between major and minor ticks
+        // remove overlap between major and minor ticks, allowing for rounding error
+        let overlap_tolerance = (limits.1 - limits.0).abs() * 1e-9;
         let minor_ticks = minor_ticks.iter()
-            .filter(|tick| !major_ticks.contains(tick))
+            .filter(|&&tick| !major_ticks.iter().any(|&major| (major - tick).abs() <= overlap_tolerance))
             .copied()
             .collect::<Vec<_>>();
 
 
-        // get major tick labels
-        let (major_labels, multiplier, offset) = match &axis.major_tick_labels {
-            TickLabels::Manual(labels) => (labels.clone(), 0, 0.0),
-            TickLabels::On => {
+        // get major tick labels, as powers on logarithmic axes
+        let auto_major_labels = || -> Result<(Vec<String>, i32, f64), PltError> {
+            if on_powers {
+                Ok((power_labels(scale, major_ticks.as_slice()), 0, 0.0))
+            } else {
                 let modifiers = tick_modifiers(major_ticks.as_slice())?;
                 let labels = ticks_to_labels(major_ticks.as_slice(), modifiers)?;
-                (labels, modifiers.1, modifiers.0)
-            },
+                Ok((labels, modifiers.multiplier, modifiers.offset))
+            }
+        };
+        let (major_labels, multiplier, offset) = match &axis.major_tick_labels {
+            TickLabels::Manual(labels) => (labels.clone(), 0, 0.0),
+            TickLabels::Formatter(formatter) => (major_ticks.iter().map(|&tick| formatter(tick)).collect(), 0, 0.0),
+            TickLabels::MultiplesOf(base, symbol) => (multiple_labels(&major_ticks, *base, symbol)?, 0, 0.0),
+            TickLabels::On => auto_major_labels()?,
             TickLabels::None => (vec![], 0, 0.0),
             TickLabels::Auto => {
                 if is_primary {
-                    let modifiers = tick_modifiers(major_ticks.as_slice())?;
-                    let labels = ticks_to_labels(major_ticks.as_slice(), modifiers)?;
-                    (labels, modifiers.1, modifiers.0)
+                    auto_major_labels()?
                 } else {
                     (vec![], 0, 0.0)
                 }
@@ -635,6 +1418,8 @@ fn draw_subplot<B: backend::Canvas>(
         // get minor tick labels
         let minor_labels = match &axis.minor_tick_labels {
             TickLabels::Manual(labels) => labels.clone(),
+            TickLabels::Formatter(formatter) => minor_ticks.iter().map(|&tick| formatter(tick)).collect(),
+            TickLabels::MultiplesOf(base, symbol) => multiple_labels(&minor_ticks, *base, symbol)?,
             TickLabels::On => {
                 let modifiers = tick_modifiers(major_ticks.as_slice())?; // use major modifiers
                 ticks_to_labels(minor_ticks.as_slice(), modifiers)?
@@ -650,7 +1435,17 @@ fn draw_subplot<B: backend::Canvas>(
             },
         };
 
+        // when both axes on a side have grid lines, only draw them once
+        let opposite_grid = match placement {
+            AxisType::X => subplot.secondary_xaxis.grid,
+            AxisType::SecondaryX => subplot.xaxis.grid,
+            AxisType::Y => subplot.secondary_yaxis.grid,
+            AxisType::SecondaryY => subplot.yaxis.grid,
+        };
+        let owns_grid = matches!(opposite_grid, Grid::None) || subplot.grid_axes.owns_grid(placement);
+
         let (major_grid, minor_grid) = match axis.grid {
+            _ if !owns_grid => (false, false),
             Grid::None => (false, false),
             Grid::Major => (true, false),
             Grid::Full => (true, true),
@@ -666,31 +1461,50 @@ fn draw_subplot<B: backend::Canvas>(
         }
 
         // add space for tick labels if necessary
-        if !major_labels.is_empty() {
-            let tick_label_size = match placement {
-                AxisType::Y | AxisType::SecondaryY => 5 * letter_size.width,
-                AxisType::X | AxisType::SecondaryX => letter_size.height,
-            };
-            *modifier_buffer.get_mut(&placement).unwrap() += tick_label_size;
-            *tick_buffer.get_mut(&placement).unwrap() += buffer_offset;
-        } else if !minor_labels.is_empty() {
-            let tick_label_size = match placement {
-                AxisType::Y | AxisType::SecondaryY => 5 * letter_size.width,
-                AxisType::X | AxisType::SecondaryX => letter_size.height,
+        if !major_labels.is_empty() || !minor_labels.is_empty() {
+            let tick_label_size = if axis.tick_label_rotation == 0.0 {
+                match placement {
+                    AxisType::Y | AxisType::SecondaryY => 5 * tick_label_letter_size.width,
+                    AxisType::X | AxisType::SecondaryX => tick_label_letter_size.height,
+                }
+            } else {
+                // rotated labels need the extent of their rotated boxes away from the axis
+                let (sin, cos) = axis.tick_label_rotation.to_radians().sin_cos();
+                let mut tick_label_size = 0;
+                for label in major_labels.iter().chain(minor_labels.iter()) {
+                    let text_size = text_size_of(canvas, label, &tick_label_font, scaling)?;
+                    let (width, height) = (text_size.width as f64, text_size.height as f64);
+                    let extent = match placement {
+                        AxisType::Y | AxisType::SecondaryY => (width * cos).abs() + (height * sin).abs(),
+                        AxisType::X | AxisType::SecondaryX => (width * sin).abs() + (height * cos).abs(),
+                    };
+                    tick_label_size = tick_label_size.max(extent.ceil() as u32);
+                }
+                tick_label_size
             };
             *modifier_buffer.get_mut(&placement).unwrap() += tick_label_size;
             *tick_buffer.get_mut(&placement).unwrap() += buffer_offset;
         }
 
-        // add space for multiplier and offset if necessary
-        if multiplier != 0 || offset != 0.0 {
+        // fold multiplier and offset into the axis label, or add space for them if necessary
+        let label = if axis.modifier_in_label && (multiplier != 0 || offset != 0.0) {
+            let modifier = modifier_text(multiplier, offset);
+            if axis.label.is_empty() {
+                format!("[{}]", modifier)
+            } else {
+                format!("{} [{}]", axis.label, modifier)
+            }
+        } else {
+            axis.label.clone()
+        };
+        if !axis.modifier_in_label && (multiplier != 0 || offset != 0.0) {
             match placement {
                 AxisType::Y => {
-                    *modifier_buffer.get_mut(&AxisType::SecondaryX).unwrap() += letter_size.height * 2 / 3;
+                    *modifier_buffer.get_mut(&AxisType::SecondaryX).unwrap() += tick_label_letter_size.height * 2 / 3;
                     *tick_label_buffer.get_mut(&AxisType::SecondaryX).unwrap() += buffer_offset;
                 },
                 AxisType::X => {
-                    *modifier_buffer.get_mut(&AxisType::X).unwrap() += letter_size.height * 2 / 3;
+                    *modifier_buffer.get_mut(&AxisType::X).unwrap() += tick_label_letter_size.height * 2 / 3;
                     *tick_label_buffer.get_mut(&AxisType::X).unwrap() += buffer_offset;
                 },
                 _ => {},
@@ -698,9 +1512,9 @@ fn draw_subplot<B: backend::Canvas>(
         }
 
         // add space for axis label if necessary
-        if !axis.label.is_empty() {
+        if !label.is_empty() {
             //*label_buffer.get_mut(&placement).unwrap() += letter_size.height * 3 / 2;
-            *label_buffer.get_mut(&placement).unwrap() += letter_size.height;
+            *label_buffer.get_mut(&placement).unwrap() += axis_label_letter_size.height;
             *tick_label_buffer.get_mut(&placement).unwrap() += buffer_offset;
         }
 
@@ -720,7 +1534,7 @@ fn draw_subplot<B: backend::Canvas>(
         finalized_axes.insert(
             placement,
             AxisFinalized {
-                label: axis.label.clone(),
+                label,
                 major_tick_locs: major_ticks,
                 major_tick_labels: major_labels,
                 minor_tick_locs: minor_ticks,
@@ -731,6 +1545,9 @@ fn draw_subplot<B: backend::Canvas>(
                 minor_grid,
                 limits,
                 visible: axis.visible,
+                modifier_in_label: axis.modifier_in_label,
+                tick_label_rotation: axis.tick_label_rotation,
+                scale,
             },
         );
     }
@@ -738,17 +1555,103 @@ fn draw_subplot<B: backend::Canvas>(
     // add space for title
     let mut title_buffer = 0;
     if !subplot.title.is_empty() {
-        title_buffer += letter_size.height;
+        title_buffer += title_letter_size.height;
         *label_buffer.get_mut(&AxisType::SecondaryX).unwrap() += buffer_offset;
     }
 
+    // add space for legend, if it is outside of the plot area
+    let legend_labels = if subplot.legend.is_some() {
+        let mut plot_labels = subplot.plot_infos.iter().map(|info| &info.label);
+        let mut fill_labels = subplot.fill_infos.iter().map(|info| &info.label);
+        let mut bar_labels = subplot.bar_infos.iter().map(|info| &info.label);
+        let mut stem_labels = subplot.stem_infos.iter().map(|info| &info.label);
+
+        subplot.plot_order.iter()
+            .filter_map(|plot_type| match plot_type {
+                PlotType::Series => plot_labels.next(),
+                PlotType::Fill => fill_labels.next(),
+                PlotType::Bar => bar_labels.next(),
+                PlotType::Stem => stem_labels.next(),
+                PlotType::Heatmap | PlotType::Reference => None,
+            })
+            .filter(|label| !label.is_empty())
+            .collect::<Vec<_>>()
+    } else {
+        vec![]
+    };
+    let legend_padding = buffer_offset;
+    let legend_row_height = letter_size.height * 2;
+    let legend_swatch_width = letter_size.height * 2;
+    let legend_size = if legend_labels.is_empty() {
+        None
+    } else {
+        let mut label_width = 0;
+        for label in legend_labels.iter() {
+            label_width = label_width.max(text_size_of(canvas, label, &font, scaling)?.width);
+        }
+
+        Some(draw::Size {
+            width: 3 * legend_padding + legend_swatch_width + label_width,
+            height: 2 * legend_padding + legend_labels.len() as u32 * legend_row_height,
+        })
+    };
+    let legend_buffer = match (subplot.legend, legend_size) {
+        (Some(LegendPosition::Outside), Some(size)) => size.width + buffer_offset,
+        _ => 0,
+    };
+
+    // add space for colorbar, with its ticks and tick labels, to the right of the secondary y-axis
+    let colorbar_width = scale_length(subplot.format.colorbar_width, scaling);
+    let colorbar = if let Some((colormap, range)) = subplot.colorbar {
+        if range.0 == range.1 || !range.0.is_finite() || !range.1.is_finite() {
+            return Err(PltError::InvalidData(format!(
+                "colorbar range of {} to {} does not span a range of values",
+                range.0, range.1,
+            )));
+        }
+
+        let ticks = (0..COLORBAR_TICKS)
+            .map(|n| range.0 + (range.1 - range.0) * (n as f64 / (COLORBAR_TICKS - 1) as f64))
+            .collect::<Vec<_>>();
+        let modifiers = tick_modifiers(&ticks)?;
+        let tick_labels = ticks_to_labels(&ticks, modifiers)?;
+
+        let mut label_width = 0;
+        for label in tick_labels.iter() {
+            label_width = label_width.max(
+                text_size_of(canvas, label, &tick_label_font, scaling)?.width
+            );
+        }
+
+        // place the multiplier and offset above the colorbar, like the y-axis
+        let modifier = modifiers.text();
+        if !modifier.is_empty() && modifier_buffer[&AxisType::SecondaryX] == 0 {
+            *modifier_buffer.get_mut(&AxisType::SecondaryX).unwrap() += tick_label_letter_size.height * 2 / 3;
+            *tick_label_buffer.get_mut(&AxisType::SecondaryX).unwrap() += buffer_offset;
+        }
+
+        Some(ColorbarFinalized { colormap, tick_labels, modifier, label_width })
+    } else {
+        None
+    };
+    let colorbar_buffer = match &colorbar {
+        Some(colorbar) => {
+            2 * buffer_offset + colorbar_width + outer_major_tick_length + colorbar.label_width
+        },
+        None => 0,
+    };
+
     // setup figure areas
 
     let title_boundary = subplot_area.ymax - subplot_buffer[&AxisType::SecondaryX] - title_buffer;
 
     let label_boundary = draw::Area {
         xmin: subplot_area.xmin + subplot_buffer[&AxisType::Y] + label_buffer[&AxisType::Y],
-        xmax: subplot_area.xmax - subplot_buffer[&AxisType::SecondaryY] - label_buffer[&AxisType::SecondaryY],
+        xmax: subplot_area.xmax
+            - subplot_buffer[&AxisType::SecondaryY]
+            - legend_buffer
+            - colorbar_buffer
+            - label_buffer[&AxisType::SecondaryY],
         ymin: subplot_area.ymin + subplot_buffer[&AxisType::X] + label_buffer[&AxisType::X],
         ymax: title_boundary - label_buffer[&AxisType::SecondaryX],
     };
@@ -779,32 +1682,95 @@ fn draw_subplot<B: backend::Canvas>(
         ymax: tick_boundary.ymax,
     };
 
-    // set plot color
-    canvas.draw_shape(draw::ShapeDescriptor {
-        point: draw::Point {
-            x: plot_area.xmin as f64 + plot_area.xsize() as f64 / 2.0,
-            y: plot_area.ymin as f64 + plot_area.ysize() as f64 / 2.0,
-        },
-        shape: draw::Shape::Rectangle {
-            h: plot_area.ysize(),
-            w: plot_area.xsize(),
+    // with an equal aspect, shrink the plot area to equal scales, moving everything around it in as well
+    let inset = if subplot.equal_aspect {
+        aspect_inset(plot_area, &finalized_axes[&AxisType::X], &finalized_axes[&AxisType::Y])
+    } else {
+        None
+    };
+    let (title_boundary, label_boundary, modifier_boundary, tick_label_boundary, plot_area) = match inset {
+        Some((xinset, yinset)) => {
+            let inset = |area: draw::Area| draw::Area {
+                xmin: area.xmin + xinset,
+                xmax: area.xmax - xinset,
+                ymin: area.ymin + yinset,
+                ymax: area.ymax - yinset,
+            };
+
+            (
+                title_boundary - yinset,
+                inset(label_boundary),
+                inset(modifier_boundary),
+                inset(tick_label_boundary),
+                inset(plot_area),
+            )
         },
+        None => (title_boundary, label_boundary, modifier_boundary, tick_label_boundary, plot_area),
+    };
+
+    // keep only the legend entries that fit within the height of the plot area
+    let legend_rows = legend_labels.len().min(
+        (plot_area.ymax.saturating_sub(plot_area.ymin).saturating_sub(2 * (buffer_offset + legend_padding))
+            / legend_row_height.max(1)) as usize
+    );
+    let legend_size = legend_size
+        .filter(|_| legend_rows > 0)
+        .map(|size| draw::Size {
+            height: 2 * legend_padding + legend_rows as u32 * legend_row_height,
+            ..size
+        });
+
+    // place legend in a corner of the plot area, or to its right
+    let legend_area = legend_size.map(|size| {
+        let left = plot_area.xmin + buffer_offset;
+        let right = plot_area.xmax.saturating_sub(buffer_offset + size.width);
+        let top = plot_area.ymax.saturating_sub(buffer_offset);
+        let bottom = plot_area.ymin + buffer_offset + size.height;
+
+        let (xmin, ymax) = match subplot.legend.unwrap_or_default() {
+            LegendPosition::TopRight => (right, top),
+            LegendPosition::TopLeft => (left, top),
+            LegendPosition::BottomLeft => (left, bottom),
+            LegendPosition::BottomRight => (right, bottom),
+            LegendPosition::Outside => (
+                (subplot_area.xmax - subplot_buffer[&AxisType::SecondaryY]).saturating_sub(size.width),
+                plot_area.ymax,
+            ),
+        };
+
+        draw::Area {
+            xmin,
+            xmax: xmin + size.width,
+            ymin: ymax.saturating_sub(size.height),
+            ymax,
+        }
+    });
+
+    // set plot color, with the same pixel edges as the axis lines
+    let (xmin, xmax) = (plot_area.xmin as f64, plot_area.xmax as f64);
+    let (ymin, ymax) = (plot_area.ymin as f64, plot_area.ymax as f64);
+    canvas.fill_region(draw::FillDescriptor {
+        points: vec![
+            draw::Point { x: xmin, y: ymin },
+            draw::Point { x: xmax, y: ymin },
+            draw::Point { x: xmax, y: ymax },
+            draw::Point { x: xmin, y: ymax },
+        ],
         fill_color: subplot.format.plot_color,
-        line_color: Color::TRANSPARENT,
-        ..Default::default()
+        clip_area: None,
     })?;
 
     // draw grid lines
     for (placement, axis) in finalized_axes.iter() {
         // draw ticks
-        for (ticks, grid) in [
-            (&axis.major_tick_locs, &axis.major_grid),
-            (&axis.minor_tick_locs, &axis.minor_grid),
+        for (ticks, grid, color, width, dashes) in [
+            (&axis.major_tick_locs, &axis.major_grid, grid_color, line_width, &[][..]),
+            (&axis.minor_tick_locs, &axis.minor_grid, minor_grid_color, minor_grid_width, &minor_grid_dashes[..]),
         ] {
             // convert tick numbers to pixel locations
             let tick_locs = ticks.iter()
                 // convert to fraction
-                .map(|tick| (tick - axis.limits.0) / (axis.limits.1 - axis.limits.0))
+                .map(|&tick| axis.scale.fraction(tick, axis.limits))
                 // convert to pixel
                 .map(|frac| plot_area.fractional_to_point(draw::Point { x: frac, y: frac }))
                 .collect::<Vec<_>>();
@@ -816,28 +1782,29 @@ fn draw_subplot<B: backend::Canvas>(
                         AxisType::Y | AxisType::SecondaryY => draw::Line {
                             p1: draw::Point {
                                 x: plot_area.xmin as f64,
-                                y: loc.y.round(),
+                                y: snap(loc.y),
                             },
                             p2: draw::Point {
                                 x: plot_area.xmax as f64,
-                                y: loc.y.round(),
+                                y: snap(loc.y),
                             },
                         },
                         AxisType::X | AxisType::SecondaryX => draw::Line {
                             p1: draw::Point {
-                                x: loc.x.round(),
+                                x: snap(loc.x),
                                 y: plot_area.ymin as f64,
                             },
                             p2: draw::Point {
-                                x: loc.x.round(),
+                                x: snap(loc.x),
                                 y: plot_area.ymax as f64,
                             },
                         },
                     };
                     canvas.draw_line(draw::LineDescriptor {
                         line,
-                        line_color: grid_color,
-                        line_width,
+                        line_color: color,
+                        line_width: width,
+                        dashes,
                         ..Default::default()
                     })?;
                 }
@@ -847,182 +1814,627 @@ fn draw_subplot<B: backend::Canvas>(
 
     // draw data
 
-    let mut plot_info_iter = subplot.plot_infos.iter();
-    let mut fill_info_iter = subplot.fill_infos.iter();
+    let draw_order = subplot.draw_order();
+
+    // default colors are taken in the order data was added, regardless of z-order
+    let data_colors = DataColors::new(subplot);
+
+    // labeled data, with the order each was added
+    let mut legend_entries = Vec::new();
+
+    // draw all data sets by z-order, then in the order called
+    for (index, plot_type, nth) in draw_order {
+        match plot_type {
+            // draw series data
+            PlotType::Series => {
+                let (series, plot_info) = (nth, &subplot.plot_infos[nth]);
+                let colors = &data_colors.series[nth];
+
+                let xaxis = &finalized_axes[&plot_info.xaxis];
+                let yaxis = &finalized_axes[&plot_info.yaxis];
+                // each series without its own seed gets a different one, derived from the figure's
+                let plot_data = plot_info.points(seed.wrapping_add(series as u64));
+
+                // resolve formats, so the legend draws the same as the plot
+                let line = plot_info.line.as_ref().zip(colors.line).map(|(line, color)| ResolvedLine {
+                    color,
+                    width: scale_length(line.width, scaling),
+                    dashes: line_dashes(&line.style, scaling),
+                });
+                let marker = plot_info.marker.as_ref().zip(colors.marker)
+                    .map(|(marker, color)| ResolvedMarker::new(marker, color, scaling));
+                // color mapped markers each have their own fill color
+                let point_markers = plot_info.marker.as_ref()
+                    .zip(plot_info.marker_colors.as_ref())
+                    .map(|(marker, colors)| {
+                        colors.iter().map(|&color| ResolvedMarker::new(marker, color, scaling)).collect::<Vec<_>>()
+                    });
 
-    // if there is a color cycle, default to those colors, otherwise default to black for series
-    let default_color = if !subplot.format.color_cycle.is_empty() {
-        subplot.format.color_cycle.clone()
-    } else {
-        vec![default_marker_color]
-    };
-    let mut default_color = default_color.iter().cycle();
+                let errorbar_line = colors.errorbars.map(|color| ResolvedLine {
+                    color,
+                    width: line.as_ref().map_or(scale_length(Line::default().width, scaling), |line| line.width),
+                    dashes: vec![],
+                });
 
-    // if there is a color cycle, default to those colors, otherwise default to red for fill
-    let default_fill_color = if !subplot.format.color_cycle.is_empty() {
-        subplot.format.color_cycle.iter().map(|&c| Color { a: 0.5, ..c }).collect()
-    } else {
-        vec![default_fill_color]
-    };
-    let mut default_fill_color = default_fill_color.iter().cycle();
-
-    // draw all data sets in the order called
-    for plot_type in subplot.plot_order.iter() { match plot_type {
-        // draw series data
-        PlotType::Series => {
-            let plot_info = plot_info_iter.next().unwrap();
-
-            let xlim = finalized_axes[&plot_info.xaxis].limits;
-            let ylim = finalized_axes[&plot_info.yaxis].limits;
-            let plot_data = &plot_info.data;
-
-            // draw line
-            if let Some(line) = plot_info.line {
-                let line_color = if let Some(color) = line.color_override {
-                    color
-                } else {
-                    *default_color.next().unwrap()
-                };
-                let dashes = match line.style {
-                    LineStyle::Solid => vec![],
-                    LineStyle::Dashed => vec![
-                        (10.0 * scaling).into(),
-                        (10.0 * scaling).into(),
-                        (10.0 * scaling).into(),
-                        (10.0 * scaling).into(),
-                    ],
-                    LineStyle::ShortDashed => vec![
-                        (4.0 * scaling).into(),
-                        (4.0 * scaling).into(),
-                        (4.0 * scaling).into(),
-                        (4.0 * scaling).into(),
-                    ],
+                let to_point = |&(x, y): &(f64, f64)| {
+                    let xfrac = xaxis.scale.fraction(x, xaxis.limits);
+                    let yfrac = yaxis.scale.fraction(y, yaxis.limits);
+
+                    let point = plot_area.fractional_to_point(draw::Point {
+                        x: xfrac,
+                        y: yfrac,
+                    });
+
+                    if plot_info.pixel_perfect {
+                        draw::Point { x: snap(point.x), y: snap(point.y) }
+                    } else {
+                        point
+                    }
                 };
-                canvas.draw_curve(draw::CurveDescriptor {
-                    points: plot_data.data()
-                        .map(|(x, y)| {
-                            let xfrac = (x - xlim.0) / (xlim.1 - xlim.0);
-                            let yfrac = (y - ylim.0) / (ylim.1 - ylim.0);
-
-                            let point = plot_area.fractional_to_point(draw::Point {
-                                x: xfrac,
-                                y: yfrac,
-                            });
-                            if plot_info.pixel_perfect {
-                                draw::Point { x: point.x.round(), y: point.y.round() }
-                            } else {
-                                point
+
+                // hidden series still take their colors, so other colors do not change
+                if plot_info.visible {
+                    // draw line, broken at gaps of NaN values
+                    if let Some(line) = &line {
+                        for segment in plot_data.split(|(_, y)| y.is_nan()).filter(|segment| !segment.is_empty()) {
+                            line.draw(canvas, segment.iter().map(to_point).collect(), plot_area)?;
+                        }
+                    }
+
+                    // draw error bars, between the line and markers
+                    if let (Some(errorbars), Some(errorbar_line)) = (&plot_info.errorbars, &errorbar_line) {
+                        let half_cap = 0.5 * errorbars.cap_size as f64 * scaling as f64;
+
+                        for (i, &(x, y)) in plot_data.iter().enumerate().filter(|(_, (_, y))| !y.is_nan()) {
+                            let (xerr, yerr) = errorbars.errors(i);
+
+                            if yerr > 0.0 {
+                                let (lower, upper) = (to_point(&(x, y - yerr)), to_point(&(x, y + yerr)));
+                                errorbar_line.draw(canvas, vec![lower, upper], plot_area)?;
+
+                                if half_cap > 0.0 {
+                                    for end in [lower, upper] {
+                                        errorbar_line.draw(canvas, vec![
+                                            draw::Point { x: end.x - half_cap, y: end.y },
+                                            draw::Point { x: end.x + half_cap, y: end.y },
+                                        ], plot_area)?;
+                                    }
+                                }
                             }
-                        })
-                        .collect::<Vec<_>>(),
-                    line_color,
-                    line_width: line.width * scaling.round() as u32,
-                    dashes: dashes.as_slice(),
-                    clip_area: Some(plot_area),
-                })?;
-            }
+                            if xerr > 0.0 {
+                                let (left, right) = (to_point(&(x - xerr, y)), to_point(&(x + xerr, y)));
+                                errorbar_line.draw(canvas, vec![left, right], plot_area)?;
+
+                                if half_cap > 0.0 {
+                                    for end in [left, right] {
+                                        errorbar_line.draw(canvas, vec![
+                                            draw::Point { x: end.x, y: end.y - half_cap },
+                                            draw::Point { x: end.x, y: end.y + half_cap },
+                                        ], plot_area)?;
+                                    }
+                                }
+                            }
+                        }
+                    }
 
-            // draw markers
-            if let Some(marker) = &plot_info.marker {
-                let mut shape = match marker.style {
-                    MarkerStyle::Circle => draw::Shape::Circle { r: marker.size },
-                    MarkerStyle::Square => draw::Shape::Square { l: marker.size },
-                };
-                shape.scale(scaling.round() as u32);
-                let fill_color = if let Some(color) = marker.color_override {
-                    color
-                } else {
-                    *default_color.next().unwrap()
-                };
-                let line = if marker.outline {
-                    marker.outline_format
-                } else {
-                    Line {
-                        style: LineStyle::Solid,
-                        width: Line::default().width,
-                        color_override: Some(Color::TRANSPARENT),
+                    // draw markers
+                    if let Some(point_markers) = &point_markers {
+                        for (point, marker) in iter::zip(&plot_data, point_markers).filter(|((_, y), _)| !y.is_nan()) {
+                            marker.draw(canvas, to_point(point), plot_area)?;
+                        }
+                    } else if let Some(marker) = &marker {
+                        for point in plot_data.iter().filter(|(_, y)| !y.is_nan()) {
+                            marker.draw(canvas, to_point(point), plot_area)?;
+                        }
                     }
-                };
-                let line_color = if let Some(color) = line.color_override {
-                    color
-                } else {
-                    fill_color
-                };
-                let line_dashes = match line.style {
-                    LineStyle::Solid => vec![],
-                    LineStyle::Dashed => vec![
-                        (10.0 * scaling).into(),
-                        (10.0 * scaling).into(),
-                        (10.0 * scaling).into(),
-                        (10.0 * scaling).into(),
-                    ],
-                    LineStyle::ShortDashed => vec![
-                        (4.0 * scaling).into(),
-                        (4.0 * scaling).into(),
-                        (4.0 * scaling).into(),
-                        (4.0 * scaling).into(),
-                    ],
-                };
-                for point in plot_data.data().map(|(x, y)| {
-                    let xfrac = (x - xlim.0) / (xlim.1 - xlim.0);
-                    let yfrac = (y - ylim.0) / (ylim.1 - ylim.0);
+                }
+
+                if !plot_info.label.is_empty() {
+                    legend_entries.push((index, &plot_info.label, LegendSwatch::Series { line, marker }));
+                }
+            }
+            // draw fill data
+            PlotType::Fill => {
+                let fill_info = &subplot.fill_infos[nth];
+
+                let xaxis = &finalized_axes[&fill_info.xaxis];
+                let yaxis = &finalized_axes[&fill_info.yaxis];
+                let color = data_colors.fills[nth];
+                let data = &fill_info.data;
+
+                let to_point = |(x, y): (f64, f64)| {
+                    let xfrac = xaxis.scale.fraction(x, xaxis.limits);
+                    let yfrac = yaxis.scale.fraction(y, yaxis.limits);
 
                     let point = plot_area.fractional_to_point(draw::Point {
                         x: xfrac,
                         y: yfrac,
                     });
 
-                    if plot_info.pixel_perfect {
-                        draw::Point { x: point.x.round(), y: point.y.round() }
+                    if fill_info.pixel_perfect {
+                        draw::Point { x: snap(point.x), y: snap(point.y) }
                     } else {
                         point
                     }
-                }) {
-                    canvas.draw_shape(draw::ShapeDescriptor {
-                        point,
-                        shape,
-                        fill_color,
-                        line_color,
-                        line_width: line.width * scaling.round() as u32,
-                        line_dashes: line_dashes.as_slice(),
+                };
+
+                let edge = fill_info.edge.as_ref().map(|edge| ResolvedLine {
+                    color: edge.color_override.unwrap_or(Color { a: 1.0, ..color }),
+                    width: scale_length(edge.width, scaling),
+                    dashes: line_dashes(&edge.style, scaling),
+                });
+
+                if fill_info.visible {
+                    let shape_points: Vec<_> = Iterator::chain(data.curve1(), data.curve2().rev())
+                        .map(to_point)
+                        .collect();
+
+                    canvas.fill_region(draw::FillDescriptor {
+                        points: shape_points,
+                        fill_color: color,
                         clip_area: Some(plot_area),
                     })?;
+
+                    // draw edges along both curves
+                    if let Some(edge) = &edge {
+                        for curve in [data.curve1(), data.curve2()] {
+                            edge.draw(canvas, curve.map(to_point).collect(), plot_area)?;
+                        }
+                    }
+                }
+
+                if !fill_info.label.is_empty() {
+                    legend_entries.push((index, &fill_info.label, LegendSwatch::Fill { color, edge }));
                 }
             }
-        }
-        // draw fill data
-        PlotType::Fill => {
-            let fill_info = fill_info_iter.next().unwrap();
-
-            let xlim = finalized_axes[&fill_info.xaxis].limits;
-            let ylim = finalized_axes[&fill_info.yaxis].limits;
-            //let color = fill_info.color;
-            let color = if let Some(color) = fill_info.color_override {
-                color
-            } else {
-                *default_fill_color.next().unwrap()
-            };
-            let data = &fill_info.data;
+            // draw bar data
+            PlotType::Bar => {
+                let bar_info = &subplot.bar_infos[nth];
+
+                let xaxis = &finalized_axes[&bar_info.xaxis];
+                let yaxis = &finalized_axes[&bar_info.yaxis];
+                let color = data_colors.bars[nth];
+
+                // bars have straight edges, so snap them to the nearest dot
+                let to_point = |(x, y): (f64, f64)| {
+                    let xfrac = xaxis.scale.fraction(x, xaxis.limits);
+                    let yfrac = yaxis.scale.fraction(y, yaxis.limits);
 
-            let shape_points: Vec<_> = Iterator::chain(data.curve1(), data.curve2().rev())
-                .map(|(x, y)| {
-                    let xfrac = (x - xlim.0) / (xlim.1 - xlim.0);
-                    let yfrac = (y - ylim.0) / (ylim.1 - ylim.0);
+                    let point = plot_area.fractional_to_point(draw::Point {
+                        x: xfrac,
+                        y: yfrac,
+                    });
+
+                    draw::Point { x: snap(point.x), y: snap(point.y) }
+                };
+
+                if bar_info.visible {
+                    for (lower, upper) in bar_info.bars() {
+                        let (lower, upper) = (to_point(lower), to_point(upper));
+
+                        canvas.fill_region(draw::FillDescriptor {
+                            points: vec![
+                                lower,
+                                draw::Point { x: upper.x, y: lower.y },
+                                upper,
+                                draw::Point { x: lower.x, y: upper.y },
+                            ],
+                            fill_color: color,
+                            clip_area: Some(plot_area),
+                        })?;
+                    }
+                }
+
+                if !bar_info.label.is_empty() {
+                    legend_entries.push((index, &bar_info.label, LegendSwatch::Fill { color, edge: None }));
+                }
+            }
+            // draw stem data
+            PlotType::Stem => {
+                let stem_info = &subplot.stem_infos[nth];
+
+                let xaxis = &finalized_axes[&stem_info.xaxis];
+                let yaxis = &finalized_axes[&stem_info.yaxis];
+
+                let (line_color, marker_color) = data_colors.stems[nth];
+                let line = ResolvedLine {
+                    color: line_color,
+                    width: scale_length(stem_info.line.width, scaling),
+                    dashes: line_dashes(&stem_info.line.style, scaling),
+                };
+                let marker = ResolvedMarker::new(&stem_info.marker, marker_color, scaling);
+
+                let to_point = |(x, y): (f64, f64)| {
+                    let xfrac = xaxis.scale.fraction(x, xaxis.limits);
+                    let yfrac = yaxis.scale.fraction(y, yaxis.limits);
 
                     plot_area.fractional_to_point(draw::Point {
                         x: xfrac,
                         y: yfrac,
                     })
-                })
-                .collect();
+                };
+
+                if stem_info.visible {
+                    // draw all stems before markers, so markers are not covered by neighboring stems
+                    for (x, y) in stem_info.data.data() {
+                        line.draw(canvas, vec![to_point((x, stem_info.baseline)), to_point((x, y))], plot_area)?;
+                    }
+                    for point in stem_info.data.data() {
+                        marker.draw(canvas, to_point(point), plot_area)?;
+                    }
+                }
+
+                if !stem_info.label.is_empty() {
+                    legend_entries.push((index, &stem_info.label, LegendSwatch::Series {
+                        line: Some(line),
+                        marker: Some(marker),
+                    }));
+                }
+            }
+            // draw heatmap data
+            PlotType::Heatmap => {
+                let heatmap_info = &subplot.heatmap_infos[nth];
+
+                let xaxis = &finalized_axes[&heatmap_info.xaxis];
+                let yaxis = &finalized_axes[&heatmap_info.yaxis];
+
+                // cells share snapped edges, so neighboring cells do not blend or leave gaps
+                let to_point = |(x, y): (f64, f64)| {
+                    let xfrac = xaxis.scale.fraction(x, xaxis.limits);
+                    let yfrac = yaxis.scale.fraction(y, yaxis.limits);
+
+                    let point = plot_area.fractional_to_point(draw::Point {
+                        x: xfrac,
+                        y: yfrac,
+                    });
+
+                    draw::Point { x: snap(point.x), y: snap(point.y) }
+                };
+
+                if heatmap_info.visible {
+                    for ((x, y), color) in heatmap_info.cells() {
+                        let (lower, upper) = (to_point((x, y)), to_point((x + 1.0, y + 1.0)));
+
+                        canvas.fill_region(draw::FillDescriptor {
+                            points: vec![
+                                lower,
+                                draw::Point { x: upper.x, y: lower.y },
+                                upper,
+                                draw::Point { x: lower.x, y: upper.y },
+                            ],
+                            fill_color: color,
+                            clip_area: Some(plot_area),
+                        })?;
+                    }
+                }
+            }
+            // draw reference lines and bands, across the plot area
+            PlotType::Reference => {
+                let reference_info = &subplot.reference_infos[nth];
+
+                if !reference_info.visible {
+                    continue;
+                }
+
+                let xaxis = &finalized_axes[&AxisType::X];
+                let yaxis = &finalized_axes[&AxisType::Y];
+
+                // the edges of the plot area, or of a band, in dots
+                let (xmin, xmax) = (plot_area.xmin as f64, plot_area.xmax as f64);
+                let (ymin, ymax) = (plot_area.ymin as f64, plot_area.ymax as f64);
+                let to_x = |x| plot_area.fractional_to_point(draw::Point {
+                    x: xaxis.scale.fraction(x, xaxis.limits),
+                    y: 0.0,
+                }).x;
+                let to_y = |y| plot_area.fractional_to_point(draw::Point {
+                    x: 0.0,
+                    y: yaxis.scale.fraction(y, yaxis.limits),
+                }).y;
+
+                match reference_info.kind {
+                    ReferenceKind::Line(orientation, value) => {
+                        let line = ResolvedLine {
+                            color: reference_info.line.color_override.unwrap_or(line_color),
+                            width: scale_length(reference_info.line.width, scaling),
+                            dashes: line_dashes(&reference_info.line.style, scaling),
+                        };
+                        // snapped, like grid lines
+                        let points = match orientation {
+                            Orientation::Horizontal => {
+                                let y = snap(to_y(value));
+                                vec![draw::Point { x: xmin, y }, draw::Point { x: xmax, y }]
+                            },
+                            Orientation::Vertical => {
+                                let x = snap(to_x(value));
+                                vec![draw::Point { x, y: ymin }, draw::Point { x, y: ymax }]
+                            },
+                        };
+
+                        // values that cannot be placed on the axis, such as nonpositive values on log axes, are skipped
+                        if points.iter().all(|point| point.x.is_finite() && point.y.is_finite()) {
+                            line.draw(canvas, points, plot_area)?;
+                        }
+                    }
+                    ReferenceKind::Span(orientation, (low, high)) => {
+                        let color = reference_info.line.color_override.unwrap_or(Color {
+                            a: REFERENCE_SPAN_ALPHA,
+                            ..line_color
+                        });
+                        let (lower, upper) = match orientation {
+                            Orientation::Horizontal => (
+                                draw::Point { x: xmin, y: snap(to_y(low)) },
+                                draw::Point { x: xmax, y: snap(to_y(high)) },
+                            ),
+                            Orientation::Vertical => (
+                                draw::Point { x: snap(to_x(low)), y: ymin },
+                                draw::Point { x: snap(to_x(high)), y: ymax },
+                            ),
+                        };
+
+                        if [lower, upper].iter().all(|point| point.x.is_finite() && point.y.is_finite()) {
+                            canvas.fill_region(draw::FillDescriptor {
+                                points: vec![
+                                    lower,
+                                    draw::Point { x: upper.x, y: lower.y },
+                                    upper,
+                                    draw::Point { x: lower.x, y: upper.y },
+                                ],
+                                fill_color: color,
+                                clip_area: Some(plot_area),
+                            })?;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // draw annotations over the data
+    let (xaxis, yaxis) = (&finalized_axes[&AxisType::X], &finalized_axes[&AxisType::Y]);
+    let to_point = |(x, y): (f64, f64)| plot_area.fractional_to_point(draw::Point {
+        x: xaxis.scale.fraction(x, xaxis.limits),
+        y: yaxis.scale.fraction(y, yaxis.limits),
+    });
+    for annotation in subplot.annotations.iter() {
+        let point = to_point(annotation.position);
+        let color = annotation.color_override.unwrap_or(font_color);
+
+        let text = draw::TextDescriptor {
+            text: annotation.text.clone(),
+            font: draw::Font {
+                name: font_name.clone(),
+                size: annotation.font_size.unwrap_or(subplot.format.font_size) * scaling,
+                ..Default::default()
+            },
+            position: draw::Point {
+                x: point.x + annotation.offset.0 * scaling as f64,
+                y: point.y + annotation.offset.1 * scaling as f64,
+            },
+            color,
+            // text is rotated clockwise
+            rotation: -annotation.rotation.to_radians(),
+            alignment: annotation.alignment,
+            clip_area: Some(plot_area),
+        };
+
+        if let Some(target) = annotation.arrow_target {
+            let tip = to_point(target);
+
+            // start the arrow where it leaves a box around the text, so it does not cross the text
+            let (lower, upper) = text_bounds(canvas, &text)?;
+            let gap = scale_length(ARROW_GAP, scaling) as f64;
+            let center = draw::Point { x: (lower.x + upper.x) / 2.0, y: (lower.y + upper.y) / 2.0 };
+            let (half_width, half_height) = ((upper.x - lower.x) / 2.0 + gap, (upper.y - lower.y) / 2.0 + gap);
+            let (dx, dy) = (tip.x - center.x, tip.y - center.y);
+            let t = f64::min(half_width / dx.abs(), half_height / dy.abs());
+
+            // arrows pointing into their own text are not drawn
+            if t < 1.0 {
+                let start = draw::Point { x: center.x + dx * t, y: center.y + dy * t };
+                let head_size = (
+                    scale_length(ARROW_HEAD_SIZE.0, scaling) as f64,
+                    scale_length(ARROW_HEAD_SIZE.1, scaling) as f64,
+                );
+
+                draw_arrow(
+                    canvas,
+                    start,
+                    tip,
+                    line_width,
+                    head_size,
+                    annotation.arrow_color.unwrap_or(color),
+                    plot_area,
+                )?;
+            }
+        }
+
+        draw_text(canvas, strict, text)?;
+    }
+
+    // draw legend
+    if let Some(legend_area) = legend_area {
+        // clip to the plot area, unless the legend is outside of it
+        let clip_area = match subplot.legend {
+            Some(LegendPosition::Outside) => *subplot_area,
+            _ => plot_area,
+        };
+
+        let (xmin, xmax) = (legend_area.xmin as f64, legend_area.xmax as f64);
+        let (ymin, ymax) = (legend_area.ymin as f64, legend_area.ymax as f64);
+        let corners = vec![
+            draw::Point { x: xmin, y: ymin },
+            draw::Point { x: xmax, y: ymin },
+            draw::Point { x: xmax, y: ymax },
+            draw::Point { x: xmin, y: ymax },
+        ];
+        canvas.fill_region(draw::FillDescriptor {
+            points: corners.clone(),
+            fill_color: subplot.format.plot_color,
+            clip_area: Some(clip_area),
+        })?;
+        // overlap the first segment, so that every corner is joined
+        canvas.draw_curve(draw::CurveDescriptor {
+            points: corners.iter().chain(corners.iter().take(2)).copied().collect(),
+            line_color: subplot.format.line_color,
+            line_width,
+            dashes: &[],
+            clip_area: Some(clip_area),
+        })?;
+
+        // stack entries from the top, each with a swatch and label
+        legend_entries.sort_by_key(|&(index, ..)| index);
+        for (i, (_, label, swatch)) in legend_entries.into_iter().take(legend_rows).enumerate() {
+            let row_top = legend_area.ymax
+                .saturating_sub(legend_padding)
+                .saturating_sub(i as u32 * legend_row_height);
+            let y = row_top as f64 - legend_row_height as f64 / 2.0;
+            let swatch_xmin = (legend_area.xmin + legend_padding) as f64;
+            let swatch_xmax = swatch_xmin + legend_swatch_width as f64;
+
+            match swatch {
+                LegendSwatch::Series { line, marker } => {
+                    if let Some(line) = line {
+                        line.draw(
+                            canvas,
+                            vec![draw::Point { x: swatch_xmin, y }, draw::Point { x: swatch_xmax, y }],
+                            clip_area,
+                        )?;
+                    }
+                    if let Some(marker) = marker {
+                        marker.draw(canvas, draw::Point { x: (swatch_xmin + swatch_xmax) / 2.0, y }, clip_area)?;
+                    }
+                },
+                LegendSwatch::Fill { color, edge } => {
+                    let half_height = (letter_size.height / 2) as f64;
+                    let corners = vec![
+                        draw::Point { x: swatch_xmin, y: y - half_height },
+                        draw::Point { x: swatch_xmax, y: y - half_height },
+                        draw::Point { x: swatch_xmax, y: y + half_height },
+                        draw::Point { x: swatch_xmin, y: y + half_height },
+                    ];
+                    canvas.fill_region(draw::FillDescriptor {
+                        points: corners.clone(),
+                        fill_color: color,
+                        clip_area: Some(clip_area),
+                    })?;
+                    if let Some(edge) = edge {
+                        edge.draw(
+                            canvas,
+                            corners.iter().chain(corners.iter().take(2)).copied().collect(),
+                            clip_area,
+                        )?;
+                    }
+                },
+            }
+
+            draw_text(canvas, strict, draw::TextDescriptor {
+                text: label.clone(),
+                position: draw::Point { x: swatch_xmax + legend_padding as f64, y },
+                alignment: draw::Alignment::Left,
+                color: font_color,
+                font: draw::Font {
+                    name: font_name.clone(),
+                    size: font_size,
+                    ..Default::default()
+                },
+                clip_area: Some(clip_area),
+                ..Default::default()
+            })?;
+        }
+    }
+
+    // draw colorbar, with its top and bottom aligned with the plot area
+    if let Some(colorbar) = colorbar {
+        let colorbar_area = draw::Area {
+            xmin: label_boundary.xmax + label_buffer[&AxisType::SecondaryY] + buffer_offset,
+            xmax: label_boundary.xmax + label_buffer[&AxisType::SecondaryY] + buffer_offset + colorbar_width,
+            ymin: plot_area.ymin,
+            ymax: plot_area.ymax,
+        };
+        let (xmin, xmax) = (colorbar_area.xmin as f64, colorbar_area.xmax as f64);
+
+        // stack slices on whole dots, so neighboring slices do not blend at their edges
+        let nslices = COLORBAR_SLICES.min(colorbar_area.ysize()).max(1);
+        for i in 0..nslices {
+            let frac = |i: u32| i as f64 / nslices as f64;
+            let ymin = snap(colorbar_area.fractional_to_point(draw::Point { x: 0.0, y: frac(i) }).y);
+            let ymax = snap(colorbar_area.fractional_to_point(draw::Point { x: 0.0, y: frac(i + 1) }).y);
 
             canvas.fill_region(draw::FillDescriptor {
-                points: shape_points,
-                fill_color: color,
-                clip_area: Some(plot_area),
+                points: vec![
+                    draw::Point { x: xmin, y: ymin },
+                    draw::Point { x: xmax, y: ymin },
+                    draw::Point { x: xmax, y: ymax },
+                    draw::Point { x: xmin, y: ymax },
+                ],
+                fill_color: colorbar.colormap.sample((i as f64 + 0.5) / nslices as f64),
+                clip_area: Some(colorbar_area),
+            })?;
+        }
+
+        // outline, overlapping the first segment so that every corner is joined
+        let (ymin, ymax) = (colorbar_area.ymin as f64, colorbar_area.ymax as f64);
+        let corners = [
+            draw::Point { x: xmin, y: ymin },
+            draw::Point { x: xmax, y: ymin },
+            draw::Point { x: xmax, y: ymax },
+            draw::Point { x: xmin, y: ymax },
+        ];
+        canvas.draw_curve(draw::CurveDescriptor {
+            points: corners.iter().chain(corners.iter().take(2)).copied().collect(),
+            line_color,
+            line_width,
+            dashes: &[],
+            clip_area: None,
+        })?;
+
+        // ticks and labels on the right side, like the secondary y-axis
+        let nticks = colorbar.tick_labels.len();
+        for (i, label) in colorbar.tick_labels.into_iter().enumerate() {
+            let frac = i as f64 / (nticks - 1) as f64;
+            let y = snap(colorbar_area.fractional_to_point(draw::Point { x: 0.0, y: frac }).y);
+
+            if inner_major_tick_length + outer_major_tick_length > 0 {
+                canvas.draw_line(draw::LineDescriptor {
+                    line: draw::Line {
+                        p1: draw::Point { x: xmax - inner_major_tick_length as f64, y },
+                        p2: draw::Point { x: xmax + outer_major_tick_length as f64, y },
+                    },
+                    line_color,
+                    line_width,
+                    ..Default::default()
+                })?;
+            }
+            draw_text(canvas, strict, draw::TextDescriptor {
+                text: label,
+                position: draw::Point {
+                    x: xmax + (outer_major_tick_length + buffer_offset) as f64,
+                    y,
+                },
+                alignment: draw::Alignment::Left,
+                color: font_color,
+                font: draw::Font {
+                    name: font_name.clone(),
+                    size: tick_label_font_size,
+                    ..Default::default()
+                },
+                ..Default::default()
             })?;
         }
-    }}
+
+        draw_text(canvas, strict, draw::TextDescriptor {
+            text: colorbar.modifier,
+            position: draw::Point { x: xmin, y: modifier_boundary.ymax as f64 },
+            alignment: draw::Alignment::BottomLeft,
+            color: font_color,
+            font: draw::Font {
+                name: font_name.clone(),
+                size: tick_label_font_size,
+                ..Default::default()
+            },
+            ..Default::default()
+        })?;
+    }
 
     // draw axis lines, labels, ticks, and tick labels for each axis
     for (placement, axis) in finalized_axes {
@@ -1085,36 +2497,30 @@ fn draw_subplot<B: backend::Canvas>(
         })?;
 
         // draw tick label modifiers if necessary
-        let mult_offset_text = if axis.label_multiplier != 0 && axis.label_offset != 0.0 {
-            let exponent = superscript(axis.label_multiplier);
-            format!("x10{} + {}", exponent, axis.label_offset)
-        } else if axis.label_multiplier != 0 {
-            let exponent = superscript(axis.label_multiplier);
-            format!("x10{}", exponent)
-        } else if axis.label_offset != 0.0 {
-            format!("+ {}", axis.label_offset)
-        } else {
+        let mult_offset_text = if axis.modifier_in_label {
             String::new()
+        } else {
+            modifier_text(axis.label_multiplier, axis.label_offset)
         };
         // determine position of modifier
         let (modifier_position, modifier_alignment) = match placement {
             AxisType::Y => (
                 draw::Point {
-                    x: plot_area.xmin as f64 - letter_size.width as f64 / 2.0,
+                    x: plot_area.xmin as f64 - tick_label_letter_size.width as f64 / 2.0,
                     y: modifier_boundary.ymax as f64,
                 },
                 draw::Alignment::BottomLeft,
             ),
             AxisType::SecondaryY => (
                 draw::Point {
-                    x: plot_area.xmax as f64 - letter_size.width as f64 / 2.0,
+                    x: plot_area.xmax as f64 - tick_label_letter_size.width as f64 / 2.0,
                     y: modifier_boundary.ymax as f64,
                 },
                 draw::Alignment::BottomLeft,
             ),
             AxisType::SecondaryX => (
                 draw::Point {
-                    x: tick_label_boundary.xmax as f64 + letter_size.width as f64,
+                    x: tick_label_boundary.xmax as f64 + tick_label_letter_size.width as f64,
                     y: tick_label_boundary.ymax as f64,
                 },
                 draw::Alignment::BottomLeft,
@@ -1127,27 +2533,23 @@ fn draw_subplot<B: backend::Canvas>(
                 draw::Alignment::TopRight,
             ),
         };
-        canvas.draw_text(draw::TextDescriptor {
+        draw_text(canvas, strict, draw::TextDescriptor {
             text: mult_offset_text,
             position: modifier_position,
             alignment: modifier_alignment,
             color: font_color,
             font: draw::Font {
                 name: font_name.clone(),
-                size: font_size,
+                size: tick_label_font_size,
                 ..Default::default()
             },
             ..Default::default()
         })?;
 
         // draw axis label
-        let label_font = draw::Font {
-            name: font_name.clone(),
-            size: font_size,
-            ..Default::default()
-        };
+        let label_font = axis_label_font.clone();
         match placement {
-            AxisType::Y => canvas.draw_text(draw::TextDescriptor {
+            AxisType::Y => draw_text(canvas, strict, draw::TextDescriptor {
                 text: axis.label,
                 position: draw::Point {
                     x: label_boundary.xmin as f64,
@@ -1159,7 +2561,7 @@ fn draw_subplot<B: backend::Canvas>(
                 font: label_font,
                 ..Default::default()
             })?,
-            AxisType::X => canvas.draw_text(draw::TextDescriptor {
+            AxisType::X => draw_text(canvas, strict, draw::TextDescriptor {
                 text: axis.label,
                 position: draw::Point {
                     x: (plot_area.xmax + plot_area.xmin) as f64 / 2.0,
@@ -1171,7 +2573,7 @@ fn draw_subplot<B: backend::Canvas>(
                 font: label_font,
                 ..Default::default()
             })?,
-            AxisType::SecondaryY => canvas.draw_text(draw::TextDescriptor {
+            AxisType::SecondaryY => draw_text(canvas, strict, draw::TextDescriptor {
                 text: axis.label,
                 position: draw::Point {
                     x: label_boundary.xmax as f64,
@@ -1183,7 +2585,7 @@ fn draw_subplot<B: backend::Canvas>(
                 font: label_font,
                 ..Default::default()
             })?,
-            AxisType::SecondaryX => canvas.draw_text(draw::TextDescriptor {
+            AxisType::SecondaryX => draw_text(canvas, strict, draw::TextDescriptor {
                 text: axis.label,
                 position: draw::Point {
                     x: (plot_area.xmax + plot_area.xmin) as f64 / 2.0,
@@ -1216,15 +2618,9 @@ fn draw_subplot<B: backend::Canvas>(
             let labels = if labels.is_empty() {
                 (0..ticks.len()).map(|_| String::new()).collect()
             } else if labels.len() != ticks.len() {
-                let axis = match placement {
-                    AxisType::Y => "y-axis",
-                    AxisType::X => "x-axis",
-                    AxisType::SecondaryY => "secondary y-axis",
-                    AxisType::SecondaryX => "secondary x-axis",
-                };
                 return Err(PltError::BadTickLabels(format!(
                     "number of tick labels does not match number of ticks on {}",
-                    axis,
+                    placement.name(),
                 )));
             } else {
                 labels
@@ -1233,7 +2629,7 @@ fn draw_subplot<B: backend::Canvas>(
             // convert tick numbers to pixel locations
             let tick_locs = ticks.iter()
                 // convert to fraction
-                .map(|tick| (tick - axis.limits.0) / (axis.limits.1 - axis.limits.0))
+                .map(|&tick| axis.scale.fraction(tick, axis.limits))
                 // convert to pixel
                 .map(|frac| plot_area.fractional_to_point(draw::Point { x: frac, y: frac }))
                 .collect::<Vec<_>>();
@@ -1246,32 +2642,32 @@ fn draw_subplot<B: backend::Canvas>(
                         draw::Line {
                             p1: draw::Point {
                                 x: (plot_area.xmin - outer_tick_length) as f64,
-                                y: loc.y.round(),
+                                y: snap(loc.y),
                             },
                             p2: draw::Point {
                                 x: (plot_area.xmin + inner_tick_length) as f64,
-                                y: loc.y.round(),
+                                y: snap(loc.y),
                             },
                         },
                         draw::Point {
                             x: tick_label_boundary.xmin as f64,
-                            y: loc.y.round(),
+                            y: snap(loc.y),
                         },
                         draw::Alignment::Right,
                     ),
                     AxisType::X => (
                         draw::Line {
                             p1: draw::Point {
-                                x: loc.x.round(),
+                                x: snap(loc.x),
                                 y: (plot_area.ymin - outer_tick_length) as f64,
                             },
                             p2: draw::Point {
-                                x: loc.x.round(),
+                                x: snap(loc.x),
                                 y: (plot_area.ymin + inner_tick_length) as f64,
                             },
                         },
                         draw::Point {
-                            x: loc.x.round(),
+                            x: snap(loc.x),
                             y: tick_label_boundary.ymin as f64,
                         },
                         draw::Alignment::Top,
@@ -1280,53 +2676,69 @@ fn draw_subplot<B: backend::Canvas>(
                         draw::Line {
                             p1: draw::Point {
                                 x: (plot_area.xmax - inner_tick_length) as f64,
-                                y: loc.y.round(),
+                                y: snap(loc.y),
                             },
                             p2: draw::Point {
                                 x: (plot_area.xmax + outer_tick_length) as f64,
-                                y: loc.y.round(),
+                                y: snap(loc.y),
                             },
                         },
                         draw::Point {
                             x: tick_label_boundary.xmax as f64,
-                            y: loc.y.round(),
+                            y: snap(loc.y),
                         },
                         draw::Alignment::Left,
                     ),
                     AxisType::SecondaryX => (
                         draw::Line {
                             p1: draw::Point {
-                                x: loc.x.round(),
+                                x: snap(loc.x),
                                 y: (plot_area.ymax - inner_tick_length) as f64,
                             },
                             p2: draw::Point {
-                                x: loc.x.round(),
+                                x: snap(loc.x),
                                 y: (plot_area.ymax + outer_tick_length) as f64,
                             },
                         },
                         draw::Point {
-                            x: loc.x.round(),
+                            x: snap(loc.x),
                             y: tick_label_boundary.ymax as f64,
                         },
                         draw::Alignment::Bottom,
                     ),
                 };
 
-                // draw line and text
-                canvas.draw_line(draw::LineDescriptor {
-                    line: tick_line,
-                    line_color,
-                    line_width,
-                    ..Default::default()
-                })?;
-                canvas.draw_text(draw::TextDescriptor {
+                // rotated x-axis labels are aligned by their corner nearest the axis,
+                // so rising labels end under their ticks, and falling labels start under them,
+                // while vertical labels stay centered on their ticks
+                let rotation = axis.tick_label_rotation.to_radians();
+                let text_alignment = match placement {
+                    _ if rotation.cos().abs() < 1e-9 => text_alignment,
+                    AxisType::X if rotation.sin() > 0.0 => draw::Alignment::TopRight,
+                    AxisType::X if rotation.sin() < 0.0 => draw::Alignment::TopLeft,
+                    AxisType::SecondaryX if rotation.sin() > 0.0 => draw::Alignment::BottomLeft,
+                    AxisType::SecondaryX if rotation.sin() < 0.0 => draw::Alignment::BottomRight,
+                    _ => text_alignment,
+                };
+
+                // draw line, unless ticks have no length, and text
+                if inner_tick_length + outer_tick_length > 0 {
+                    canvas.draw_line(draw::LineDescriptor {
+                        line: tick_line,
+                        line_color,
+                        line_width,
+                        ..Default::default()
+                    })?;
+                }
+                draw_text(canvas, strict, draw::TextDescriptor {
                     text: tick.to_string(),
                     position: text_position,
                     alignment: text_alignment,
+                    rotation: -rotation,
                     color: font_color,
                     font: draw::Font {
                         name: font_name.clone(),
-                        size: font_size,
+                        size: tick_label_font_size,
                         ..Default::default()
                     },
                     ..Default::default()
@@ -1336,7 +2748,7 @@ fn draw_subplot<B: backend::Canvas>(
     }
 
     // draw title
-    canvas.draw_text(draw::TextDescriptor {
+    draw_text(canvas, strict, draw::TextDescriptor {
         text: subplot.title.clone(),
         position: draw::Point {
             x: (plot_area.xmax + plot_area.xmin) as f64 / 2.0,
@@ -1344,11 +2756,7 @@ fn draw_subplot<B: backend::Canvas>(
         },
         alignment: draw::Alignment::Bottom,
         color: font_color,
-        font: draw::Font {
-            name: font_name,
-            size: font_size,
-            ..Default::default()
-        },
+        font: title_font,
         ..Default::default()
     })?;
 
@@ -1,12 +1,56 @@
-use crate::subplot::{Subplot, SubplotDescriptor};
+use crate::subplot::{AxisType, Subplot, SubplotDescriptor};
 use crate::PltError;
 
+use std::collections::BTreeMap;
+use std::mem;
+
 #[cfg(doc)]
-use crate::figure::Figure;
+use crate::{figure::Figure, subplot::SubplotFormat};
 
 /// Defines how and where Subplots are place in a [`Figure`].
+///
+/// Custom layouts only need to place each subplot in a [`FractionalArea`] of the figure.
+/// Areas are checked when the layout is set on a figure,
+/// but can be checked up front by building them with [`FractionalArea::new`].
+///
+/// ```
+/// use plt::{FractionalArea, Layout, PltError, Subplot};
+///
+/// /// Splits the figure into a main and side subplot, with widths in the golden ratio.
+/// struct GoldenLayout<'a> {
+///     main: Subplot<'a>,
+///     side: Subplot<'a>,
+/// }
+/// impl<'a> Layout<'a> for GoldenLayout<'a> {
+///     fn subplots(self) -> Vec<(Subplot<'a>, FractionalArea)> {
+///         let split = 1.0 / 1.618;
+///
+///         vec![
+///             (self.main, FractionalArea::new(0.0, split, 0.0, 1.0).unwrap()),
+///             (self.side, FractionalArea::new(split, 1.0, 0.0, 1.0).unwrap()),
+///         ]
+///     }
+/// }
+///
+/// let layout = GoldenLayout {
+///     main: Subplot::builder().build(),
+///     side: Subplot::builder().build(),
+/// };
+/// assert_eq!(layout.subplots().len(), 2);
+///
+/// assert!(matches!(
+///     FractionalArea::new(0.5, 0.25, 0.0, 1.0),
+///     Err(PltError::InvalidSubplotArea(_)),
+/// ));
+/// ```
 pub trait Layout<'a> {
+    /// Consumes the layout, returning each subplot and the area of the figure it is drawn in.
     fn subplots(self) -> Vec<(Subplot<'a>, FractionalArea)>;
+    /// Whether subplot areas may overlap, with later subplots drawn over earlier ones.
+    /// By default, [`Figure::set_layout_checked`] rejects overlapping areas.
+    fn allows_overlap(&self) -> bool {
+        false
+    }
 }
 
 /// A [`Layout`] in which a single subplot fills the whole figure.
@@ -28,32 +72,84 @@ impl<'a> Layout<'a> for SingleLayout<'a> {
     }
 }
 
+/// A [`Layout`] in which a main subplot fills the whole figure,
+/// with smaller inset subplots drawn over it.
+///
+/// Plot areas are filled with [`SubplotFormat::plot_color`], which is transparent by default,
+/// so insets need an opaque plot color to hide the main subplot under them.
+///
+/// ```
+/// use plt::*;
+///
+/// let mut main = Subplot::builder().build();
+/// main.plot([0.0, 1.0, 2.0], [0.0, 4.0, 1.0]).unwrap();
+///
+/// let mut zoomed = Subplot::builder()
+///     .format(SubplotFormat { plot_color: Color::WHITE, ..Default::default() })
+///     .xlimits(Limits::Manual { min: 0.5, max: 1.5 })
+///     .build();
+/// zoomed.plot([0.0, 1.0, 2.0], [0.0, 4.0, 1.0]).unwrap();
+///
+/// let mut layout = InsetLayout::new(main);
+/// layout.insert(zoomed, FractionalArea::new(0.6, 0.95, 0.6, 0.95).unwrap()).unwrap();
+///
+/// let mut fig = <Figure>::default();
+/// fig.set_layout(layout).unwrap();
+/// ```
+pub struct InsetLayout<'a> {
+    main: Subplot<'a>,
+    insets: Vec<(Subplot<'a>, FractionalArea)>,
+}
+impl<'a> InsetLayout<'a> {
+    /// The main constructor, setting the main subplot.
+    pub fn new(main: Subplot<'a>) -> Self {
+        Self { main, insets: vec![] }
+    }
+    /// Adds an inset subplot in an area of the figure, drawn over the main subplot and any earlier insets.
+    ///
+    /// Returns [`PltError::InvalidSubplotArea`] if the area is not valid.
+    pub fn insert(&mut self, subplot: Subplot<'a>, area: FractionalArea) -> Result<(), PltError> {
+        if !area.valid() {
+            return Err(PltError::InvalidSubplotArea(area));
+        }
+
+        self.insets.push((subplot, area));
+
+        Ok(())
+    }
+}
+impl<'a> Layout<'a> for InsetLayout<'a> {
+    fn subplots(self) -> Vec<(Subplot<'a>, FractionalArea)> {
+        let mut subplots = vec![(
+            self.main,
+            FractionalArea { xmin: 0.0, xmax: 1.0, ymin: 0.0, ymax: 1.0 },
+        )];
+        subplots.extend(self.insets);
+
+        subplots
+    }
+
+    fn allows_overlap(&self) -> bool {
+        true
+    }
+}
+
 /// A [`Layout`] in which subplots are placed in a grid orientation in the figure.
 pub struct GridLayout<'a> {
     subplots: ndarray::Array2<Subplot<'a>>,
     areas: ndarray::Array2<FractionalArea>,
     mask: ndarray::Array2<bool>,
+    /// The number of rows and columns covered by the subplot in each cell.
+    spans: ndarray::Array2<(usize, usize)>,
+    geometry: GridGeometry,
+    share_x: bool,
+    share_y: bool,
 }
 impl<'a> GridLayout<'a> {
     /// Creates an empty layout.
     pub fn new(nrows: usize, ncols: usize) -> Self {
-        let xextent = 1.0 / ncols as f64;
-        let yextent = 1.0 / nrows as f64;
-        let areas = (0..(nrows * ncols))
-            .map(|index| {
-                // get row and column indices
-                let row = index / ncols;
-                let col = index % ncols;
-
-                let xmin = xextent * col as f64;
-                let xmax = xmin + xextent;
-                let ymin = yextent * (nrows - 1 - row) as f64;
-                let ymax = ymin + yextent;
-
-                FractionalArea { xmin, xmax, ymin, ymax }
-            })
-            .collect::<ndarray::Array1<_>>();
-        let areas = areas.into_shape((nrows, ncols)).unwrap();
+        let geometry = GridGeometry::new(nrows, ncols);
+        let areas = geometry.areas();
 
         Self {
             subplots: ndarray::Array2::from_elem(
@@ -62,6 +158,10 @@ impl<'a> GridLayout<'a> {
             ),
             areas,
             mask: ndarray::Array2::from_elem((nrows, ncols), false),
+            spans: ndarray::Array2::from_elem((nrows, ncols), (1, 1)),
+            geometry,
+            share_x: false,
+            share_y: false,
         }
     }
     /// Creates a uniform grid layout from a 2D array, filling only the spots with [`Some`] subplot.
@@ -70,24 +170,8 @@ impl<'a> GridLayout<'a> {
 
         let nrows = subplots.nrows();
         let ncols = subplots.ncols();
-
-        let xextent = 1.0 / ncols as f64;
-        let yextent = 1.0 / nrows as f64;
-        let areas = (0..(nrows * ncols))
-            .map(|index| {
-                // get row and column indices
-                let row = index / ncols;
-                let col = index % ncols;
-
-                let xmin = xextent * col as f64;
-                let xmax = xmin + xextent;
-                let ymin = yextent * (nrows - 1 - row) as f64;
-                let ymax = ymin + yextent;
-
-                FractionalArea { xmin, xmax, ymin, ymax }
-            })
-            .collect::<ndarray::Array1<_>>();
-        let areas = areas.into_shape((nrows, ncols)).unwrap();
+        let geometry = GridGeometry::new(nrows, ncols);
+        let areas = geometry.areas();
 
         let mask = subplots.map(|subplot| subplot.is_some());
         let subplots = subplots.mapv(|subplot| {
@@ -98,7 +182,109 @@ impl<'a> GridLayout<'a> {
             subplots,
             areas,
             mask,
+            spans: ndarray::Array2::from_elem((nrows, ncols), (1, 1)),
+            geometry,
+            share_x: false,
+            share_y: false,
+        }
+    }
+    /// Sets the gaps left between neighboring rows and columns, as fractions of the figure height and width.
+    /// Defaults to no gaps, so neighboring cells touch.
+    pub fn spacing(mut self, hspace: f64, wspace: f64) -> Self {
+        self.geometry.hspace = hspace;
+        self.geometry.wspace = wspace;
+        self.update_areas();
+
+        self
+    }
+    /// Sets the space left around the outside of the grid, as fractions of the figure height and width.
+    /// Defaults to no space, so the grid fills the figure.
+    pub fn margins(mut self, top: f64, bottom: f64, left: f64, right: f64) -> Self {
+        self.geometry.bounds = FractionalArea {
+            xmin: left,
+            xmax: 1.0 - right,
+            ymin: bottom,
+            ymax: 1.0 - top,
+        };
+        self.update_areas();
+
+        self
+    }
+    /// Shares x-axis limits between subplots covering the same columns,
+    /// and only labels the x-axis ticks of the bottom subplot in each column.
+    ///
+    /// When the layout is set on a figure, automatic x-axis limits of subplots in a column fit the union
+    /// of their data, from the minimum of their minimums to the maximum of their maximums,
+    /// so the columns line up. Axes with manual limits keep them and their tick labels,
+    /// and do not change the shared span.
+    /// Subplots spanning several columns only share with subplots spanning the same columns.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let mut top = Subplot::builder().build();
+    /// top.plot([0.0, 1.0, 2.0], [0.0, 4.0, 1.0]).unwrap();
+    /// let mut bottom = Subplot::builder().build();
+    /// bottom.plot([1.0, 5.0], [2.0, 3.0]).unwrap();
+    ///
+    /// // both subplots have x-axis limits fit to data from 0.0 to 5.0
+    /// let mut layout = GridLayout::new(2, 1).share_x();
+    /// layout.insert((0, 0), top).unwrap();
+    /// layout.insert((1, 0), bottom).unwrap();
+    ///
+    /// // with the same margins around the shared span
+    /// let subplots = layout.subplots();
+    /// assert_eq!(subplots[0].0.limits(Axes::X), Some((-0.25, 5.25)));
+    /// assert_eq!(subplots[1].0.limits(Axes::X), Some((-0.25, 5.25)));
+    /// ```
+    pub fn share_x(mut self) -> Self {
+        self.share_x = true;
+
+        self
+    }
+    /// Shares y-axis limits between subplots covering the same rows,
+    /// and only labels the y-axis ticks of the leftmost subplot in each row.
+    ///
+    /// Limits are shared like [`GridLayout::share_x`] shares them across columns.
+    pub fn share_y(mut self) -> Self {
+        self.share_y = true;
+
+        self
+    }
+    /// Sets the relative heights of rows, from top to bottom, and widths of columns, from left to right.
+    /// Defaults to equal heights and widths.
+    ///
+    /// Returns [`PltError::InvalidData`] if there is not one ratio for each row and column,
+    /// or if a ratio is not a positive number.
+    ///
+    /// ```
+    /// use plt::GridLayout;
+    ///
+    /// // a main subplot with narrow marginal subplots above it and to its right
+    /// let layout = GridLayout::new(2, 2).ratios(vec![1.0, 4.0], vec![4.0, 1.0]).unwrap();
+    /// assert!(GridLayout::new(2, 2).ratios(vec![1.0], vec![1.0, 1.0]).is_err());
+    /// ```
+    pub fn ratios(mut self, row_ratios: Vec<f64>, col_ratios: Vec<f64>) -> Result<Self, PltError> {
+        let (nrows, ncols) = self.dims();
+        if row_ratios.len() != nrows {
+            return Err(PltError::InvalidData(format!(
+                "{} row ratios given for a grid with {} rows", row_ratios.len(), nrows,
+            )));
+        } else if col_ratios.len() != ncols {
+            return Err(PltError::InvalidData(format!(
+                "{} column ratios given for a grid with {} columns", col_ratios.len(), ncols,
+            )));
+        } else if let Some(ratio) = row_ratios.iter().chain(col_ratios.iter())
+            .find(|ratio| !(ratio.is_finite() && **ratio > 0.0))
+        {
+            return Err(PltError::InvalidData(format!("grid ratio of {} is not a positive number", ratio)));
         }
+
+        self.geometry.row_ratios = row_ratios;
+        self.geometry.col_ratios = col_ratios;
+        self.update_areas();
+
+        Ok(self)
     }
     /// Adds or replaces a subplot at the specified location.
     pub fn insert(
@@ -114,13 +300,170 @@ impl<'a> GridLayout<'a> {
         }
 
         self.subplots[[row, col]] = subplot;
+        self.areas[[row, col]] = self.span_area((row, col), (1, 1));
+        self.spans[[row, col]] = (1, 1);
+        self.mask[[row, col]] = true;
+
+        Ok(())
+    }
+    /// Adds or replaces a subplot spanning several cells, with its top left cell at the specified location.
+    /// Cells covered by the span are emptied, so they are not drawn beneath it.
+    ///
+    /// ```
+    /// use plt::{GridLayout, Subplot};
+    ///
+    /// // a wide subplot across the top, above two narrow ones
+    /// let mut layout = GridLayout::new(2, 2);
+    /// layout.insert_span((0, 0), (1, 2), Subplot::builder().build()).unwrap();
+    /// layout.insert((1, 0), Subplot::builder().build()).unwrap();
+    /// layout.insert((1, 1), Subplot::builder().build()).unwrap();
+    ///
+    /// assert_eq!(layout.occupied(), vec![(0, 0), (1, 0), (1, 1)]);
+    /// assert!(layout.insert_span((1, 1), (1, 2), Subplot::builder().build()).is_err());
+    /// ```
+    pub fn insert_span(
+        &mut self,
+        (row, col): (usize, usize),
+        (rowspan, colspan): (usize, usize),
+        subplot: Subplot<'a>,
+    ) -> Result<(), PltError> {
+        let (nrows, ncols) = self.dims();
+        if rowspan == 0 || colspan == 0 {
+            return Err(PltError::InvalidData(format!(
+                "span of {} rows and {} columns does not cover a cell", rowspan, colspan,
+            )));
+        }
+        if (row + rowspan) > nrows {
+            return Err(PltError::InvalidRow { row: row + rowspan - 1, nrows });
+        }
+        if (col + colspan) > ncols {
+            return Err(PltError::InvalidColumn { col: col + colspan - 1, ncols });
+        }
+
+        // empty the covered cells, including any subplot previously at the top left
+        for covered_row in row..(row + rowspan) {
+            for covered_col in col..(col + colspan) {
+                self.remove((covered_row, covered_col))?;
+            }
+        }
+
+        self.subplots[[row, col]] = subplot;
+        self.areas[[row, col]] = self.span_area((row, col), (rowspan, colspan));
+        self.spans[[row, col]] = (rowspan, colspan);
         self.mask[[row, col]] = true;
 
         Ok(())
     }
+    /// Removes the subplot at the specified location, leaving the cell empty.
+    /// Returns the removed subplot, if the cell held one.
+    pub fn remove(&mut self, (row, col): (usize, usize)) -> Result<Option<Subplot<'a>>, PltError> {
+        if (row + 1) > self.subplots.nrows() {
+            return Err(PltError::InvalidRow { row, nrows: self.subplots.nrows() });
+        }
+        if (col + 1) > self.subplots.ncols() {
+            return Err(PltError::InvalidColumn { col, ncols: self.subplots.ncols() });
+        }
+
+        let subplot = mem::replace(
+            &mut self.subplots[[row, col]],
+            Subplot::new(&SubplotDescriptor::default()),
+        );
+        let filled = mem::replace(&mut self.mask[[row, col]], false);
+        self.areas[[row, col]] = self.span_area((row, col), (1, 1));
+        self.spans[[row, col]] = (1, 1);
+
+        Ok(if filled { Some(subplot) } else { None })
+    }
+    /// Checks that no two occupied cells cover overlapping areas of the figure.
+    pub fn validate(&self) -> Result<(), PltError> {
+        let areas = self.areas.indexed_iter()
+            .filter_map(|(index, &area)| if self.mask[index] { Some(area) } else { None })
+            .collect::<Vec<_>>();
+
+        if let Some((area, other)) = find_overlap(&areas) {
+            return Err(PltError::OverlappingSubplots(area, other));
+        }
+
+        Ok(())
+    }
+    /// Returns the (row, column) locations that hold a subplot, in row-major order.
+    pub fn occupied(&self) -> Vec<(usize, usize)> {
+        self.mask.indexed_iter()
+            .filter_map(|(index, &filled)| if filled { Some(index) } else { None })
+            .collect()
+    }
+    /// Returns the number of rows and columns in the grid.
+    pub fn dims(&self) -> (usize, usize) {
+        self.mask.dim()
+    }
+}
+impl GridLayout<'_> {
+    /// The area covered by a span of cells, from the top left of its first cell to the bottom right of its last.
+    fn span_area(&self, (row, col): (usize, usize), (rowspan, colspan): (usize, usize)) -> FractionalArea {
+        let first = self.geometry.area((row, col));
+        let last = self.geometry.area((row + rowspan - 1, col + colspan - 1));
+
+        FractionalArea {
+            xmin: first.xmin,
+            xmax: last.xmax,
+            ymin: last.ymin,
+            ymax: first.ymax,
+        }
+    }
+    /// Recomputes the area of each cell, after the geometry of the grid changes.
+    fn update_areas(&mut self) {
+        self.areas = ndarray::Array2::from_shape_fn(self.dims(), |index| self.span_area(index, self.spans[index]));
+    }
+    /// Shares the span of the x-axis between subplots covering the same columns, or of the y-axis between
+    /// subplots covering the same rows, and hides tick labels of all but the bottom or leftmost subplot.
+    fn share(&mut self, placement: AxisType) {
+        // group subplots by the columns or rows they cover
+        let mut groups = BTreeMap::<(usize, usize), Vec<(usize, usize)>>::new();
+        for (row, col) in self.occupied() {
+            let (rowspan, colspan) = self.spans[[row, col]];
+            let group = if placement == AxisType::X { (col, colspan) } else { (row, rowspan) };
+            groups.entry(group).or_default().push((row, col));
+        }
+
+        for members in groups.values() {
+            // the shared span runs from the minimum of minimums to the maximum of maximums
+            let span = members.iter()
+                .filter_map(|&index| self.subplots[index].auto_span(placement))
+                .reduce(|(min, max), (other_min, other_max)| (min.min(other_min), max.max(other_max)));
+            if let Some(span) = span {
+                for &index in members {
+                    self.subplots[index].share_span(placement, span);
+                }
+            }
+
+            // only the sharing subplot at the outer edge keeps its tick labels,
+            // while subplots with manual limits draw their own range, so keep theirs
+            let sharing = members.iter()
+                .filter(|&&index| !self.subplots[index].manual_limits(placement))
+                .collect::<Vec<_>>();
+            let labeled = if placement == AxisType::X {
+                sharing.iter().max_by_key(|&&&(row, col)| row + self.spans[[row, col]].0)
+            } else {
+                sharing.iter().min_by_key(|&&&(_, col)| col)
+            };
+            let labeled = labeled.copied().copied();
+            for &index in sharing {
+                if Some(index) != labeled {
+                    self.subplots[index].hide_tick_labels(placement);
+                }
+            }
+        }
+    }
 }
 impl<'a> Layout<'a> for GridLayout<'a> {
-    fn subplots(self) -> Vec<(Subplot<'a>, FractionalArea)> {
+    fn subplots(mut self) -> Vec<(Subplot<'a>, FractionalArea)> {
+        if self.share_x {
+            self.share(AxisType::X);
+        }
+        if self.share_y {
+            self.share(AxisType::Y);
+        }
+
         Iterator::zip(
             self.subplots.indexed_iter().filter_map(|(index, subplot)|
                 if self.mask[index] { Some(subplot) } else { None }
@@ -141,15 +484,84 @@ pub struct FractionalArea {
     pub ymax: f64,
 }
 impl FractionalArea {
-    pub(crate) fn to_area(self, size: draw::Size) -> draw::Area {
+    /// Creates an area from fractional boundaries, from 0.0 to 1.0.
+    ///
+    /// Returns [`PltError::InvalidSubplotArea`] if a boundary is outside of the figure,
+    /// or if a minimum is not less than its maximum.
+    pub fn new(xmin: f64, xmax: f64, ymin: f64, ymax: f64) -> Result<Self, PltError> {
+        let area = Self { xmin, xmax, ymin, ymax };
+
+        if area.valid() {
+            Ok(area)
+        } else {
+            Err(PltError::InvalidSubplotArea(area))
+        }
+    }
+    /// Converts the area to dots in a figure of some size.
+    /// Each boundary is rounded to the nearest dot, so areas sharing a boundary share the same dot,
+    /// without gaps or overlap between them.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let (nrows, ncols) = (3, 7);
+    /// let mut layout = GridLayout::new(nrows, ncols);
+    /// for row in 0..nrows {
+    ///     for col in 0..ncols {
+    ///         layout.insert((row, col), Subplot::builder().build()).unwrap();
+    ///     }
+    /// }
+    ///
+    /// // cells are listed by row, from the top left
+    /// let size = backend::Size { width: 641, height: 479 };
+    /// let areas = layout.subplots()
+    ///     .into_iter()
+    ///     .map(|(_, area)| area.to_area(size))
+    ///     .collect::<Vec<_>>();
+    /// let cell = |row: usize, col: usize| areas[row * ncols + col];
+    ///
+    /// for row in 0..nrows {
+    ///     for col in 0..ncols {
+    ///         if col + 1 < ncols {
+    ///             assert_eq!(cell(row, col).xmax, cell(row, col + 1).xmin);
+    ///         }
+    ///         if row + 1 < nrows {
+    ///             assert_eq!(cell(row, col).ymin, cell(row + 1, col).ymax);
+    ///         }
+    ///     }
+    /// }
+    /// assert_eq!((cell(0, 0).xmin, cell(0, ncols - 1).xmax), (0, size.width));
+    /// assert_eq!((cell(nrows - 1, 0).ymin, cell(0, 0).ymax), (0, size.height));
+    /// ```
+    pub fn to_area(self, size: draw::Size) -> draw::Area {
         draw::Area {
-            xmin: (self.xmin * size.width as f64).ceil() as u32,
-            xmax: (self.xmax * size.width as f64).floor() as u32,
-            ymin: (self.ymin * size.height as f64).ceil() as u32,
-            ymax: (self.ymax * size.height as f64).floor() as u32,
+            xmin: (self.xmin * size.width as f64).round() as u32,
+            xmax: (self.xmax * size.width as f64).round() as u32,
+            ymin: (self.ymin * size.height as f64).round() as u32,
+            ymax: (self.ymax * size.height as f64).round() as u32,
         }
     }
-    pub(crate) fn valid(&self) -> bool {
+    /// Shrinks the area into the part of a figure inside a margin around each edge.
+    pub(crate) fn inset(self, margin: f64) -> Self {
+        let scale = 1.0 - 2.0 * margin;
+
+        Self {
+            xmin: margin + scale * self.xmin,
+            xmax: margin + scale * self.xmax,
+            ymin: margin + scale * self.ymin,
+            ymax: margin + scale * self.ymax,
+        }
+    }
+    /// Whether the interiors of two areas overlap. Areas sharing only an edge do not overlap.
+    pub(crate) fn overlaps(&self, other: &Self) -> bool {
+        // allow for rounding where neighboring edges are computed differently
+        const TOLERANCE: f64 = 1e-9;
+
+        self.xmin < other.xmax - TOLERANCE && other.xmin < self.xmax - TOLERANCE
+            && self.ymin < other.ymax - TOLERANCE && other.ymin < self.ymax - TOLERANCE
+    }
+    /// Whether the area is inside of the figure, with each minimum less than its maximum.
+    pub fn valid(&self) -> bool {
         self.xmin >= 0.0 && self.xmin <= 1.0
             && self.xmax >= 0.0 && self.xmax <= 1.0
             && self.ymin >= 0.0 && self.ymin <= 1.0
@@ -158,3 +570,110 @@ impl FractionalArea {
             && self.ymin < self.ymax
     }
 }
+
+/// Where a subplot is drawn in a figure.
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum SubplotArea {
+    /// Fractions of the figure, rounded to whole pixels when drawn.
+    Fractional(FractionalArea),
+    /// Exact pixels, regardless of the figure size.
+    Pixel(draw::Area),
+}
+impl SubplotArea {
+    pub(crate) fn to_area(self, size: draw::Size) -> draw::Area {
+        match self {
+            Self::Fractional(area) => area.to_area(size),
+            Self::Pixel(area) => area,
+        }
+    }
+}
+
+/// The sizes of and gaps between the cells of a [`GridLayout`].
+#[derive(Clone, Debug)]
+struct GridGeometry {
+    /// The gap between rows, as a fraction of the figure height.
+    hspace: f64,
+    /// The gap between columns, as a fraction of the figure width.
+    wspace: f64,
+    /// The area of the figure the grid is placed in.
+    bounds: FractionalArea,
+    /// The relative heights of rows, from top to bottom.
+    row_ratios: Vec<f64>,
+    /// The relative widths of columns, from left to right.
+    col_ratios: Vec<f64>,
+}
+impl GridGeometry {
+    /// A grid of equal cells filling the figure, without gaps.
+    fn new(nrows: usize, ncols: usize) -> Self {
+        Self {
+            hspace: 0.0,
+            wspace: 0.0,
+            bounds: FractionalArea { xmin: 0.0, xmax: 1.0, ymin: 0.0, ymax: 1.0 },
+            row_ratios: vec![1.0; nrows],
+            col_ratios: vec![1.0; ncols],
+        }
+    }
+
+    /// The area of each cell, with rows from top to bottom.
+    fn areas(&self) -> ndarray::Array2<FractionalArea> {
+        let (xextents, yextents) = (self.column_extents(), self.row_extents());
+        let nrows = yextents.len();
+
+        ndarray::Array2::from_shape_fn((nrows, xextents.len()), |(row, col)| {
+            cell_area(xextents[col], yextents[nrows - 1 - row])
+        })
+    }
+
+    /// The area of one cell, with rows from top to bottom.
+    fn area(&self, (row, col): (usize, usize)) -> FractionalArea {
+        let (xextents, yextents) = (self.column_extents(), self.row_extents());
+
+        cell_area(xextents[col], yextents[yextents.len() - 1 - row])
+    }
+
+    /// The x-extents of columns, from left to right.
+    fn column_extents(&self) -> Vec<(f64, f64)> {
+        grid_extents(&self.col_ratios, (self.bounds.xmin, self.bounds.xmax), self.wspace)
+    }
+
+    /// The y-extents of rows, from bottom to top.
+    fn row_extents(&self) -> Vec<(f64, f64)> {
+        let bottom_to_top = self.row_ratios.iter().rev().copied().collect::<Vec<_>>();
+
+        grid_extents(&bottom_to_top, (self.bounds.ymin, self.bounds.ymax), self.hspace)
+    }
+}
+
+/// Divides a range into cells sized by ratios, with gaps between them.
+///
+/// Each boundary is computed from the ratios before it alone,
+/// so without gaps neighboring cells share exactly the same boundary.
+fn grid_extents(ratios: &[f64], (min, max): (f64, f64), gap: f64) -> Vec<(f64, f64)> {
+    let total = ratios.iter().sum::<f64>();
+    let available = max - min - gap * ratios.len().saturating_sub(1) as f64;
+
+    let mut cumulative = 0.0;
+    ratios.iter()
+        .enumerate()
+        .map(|(i, ratio)| {
+            let start = min + i as f64 * gap + available * cumulative / total;
+            cumulative += ratio;
+            let end = min + i as f64 * gap + available * cumulative / total;
+
+            (start, end)
+        })
+        .collect()
+}
+
+/// The area of a cell from its extents along x and y.
+fn cell_area((xmin, xmax): (f64, f64), (ymin, ymax): (f64, f64)) -> FractionalArea {
+    FractionalArea { xmin, xmax, ymin, ymax }
+}
+
+/// Finds the first pair of overlapping areas, if any.
+pub(crate) fn find_overlap(areas: &[FractionalArea]) -> Option<(FractionalArea, FractionalArea)> {
+    areas.iter()
+        .enumerate()
+        .flat_map(|(i, area)| areas[(i + 1)..].iter().map(move |other| (*area, *other)))
+        .find(|(area, other)| area.overlaps(other))
+}
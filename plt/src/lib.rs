@@ -42,25 +42,48 @@
 //!
 //! ### Arch
 //! `pacman -Syu cairo`
+//!
+//! # Reproducible Layout
+//!
+//! Text is measured with the installed fonts, so layout can shift slightly between machines.
+//! The `fixed-metrics` feature measures text with built-in Helvetica and Courier metrics instead,
+//! which the default FreeSans font shares, so figures are laid out the same on any machine.
+//! Other fonts, such as Times, and bold or italic text are measured as regular Helvetica,
+//! so their labels may be spaced too tightly or too loosely.
+//!
+//! Only the layout is reproducible, not the rendered pixels.
+//! Text is still drawn with the installed fonts, so comparing rendered output in tests
+//! needs the same fonts on every machine.
+//!
+//! ```toml
+//! plt = { version = "0.4", features = ["fixed-metrics"] }
+//! ```
 
+mod animation;
 mod figure;
 mod layout;
 mod subplot;
 
+pub mod ticks;
+
 // bring pub elements from submodules into main lib module
+pub use animation::*;
 pub use figure::*;
 pub use layout::*;
 pub use subplot::*;
+pub use ticks::superscript;
 
 // re-export necessary elements from plt-draw
-pub use draw::{Color, FileFormat, FontName};
+pub use draw::{
+    Alignment, Bitmap, Color, Colormap, FileFormat, Font, FontName, FontSlant, FontWeight, Normalization, Point,
+};
 
 // re-export backend canvas in separate module
 /// Re-exports of neccessary plt-draw backend elements.
 pub mod backend {
-    pub use draw::Canvas;
+    pub use draw::{Area, Canvas, ImageFormat, Size};
     #[cfg(feature = "cairo")]
-    pub use draw_cairo::CairoCanvas;
+    pub use draw_cairo::{cairo, CairoCanvas};
 }
 
 /// The error type for this library.
@@ -79,6 +102,9 @@ pub enum PltError {
     /// Returned in the case of a subplot index that is out of bounds.
     #[error("column index `{col}` is out of range for layout with {ncols} columns")]
     InvalidColumn { col: usize, ncols: usize },
+    /// Returned in the case of a series index that is out of bounds.
+    #[error("series index `{index}` is out of range for subplot with {nseries} series")]
+    InvalidSeries { index: usize, nseries: usize },
     /// Returned when tick mark locations has an unusable value.
     #[error("one or more ticks have invalid locations: `{0}`")]
     BadTickPlacement(String),
@@ -88,6 +114,24 @@ pub enum PltError {
     /// Returned when the provided area of a subplot is not valid.
     #[error("{0:?} is not a valid fractional area")]
     InvalidSubplotArea(layout::FractionalArea),
+    /// Returned when a subplot area in pixels is empty or outside of the figure.
+    #[error("{0:?} is not a valid pixel area for the figure")]
+    InvalidPixelArea(draw::Area),
+    /// Returned when the areas of two subplots overlap.
+    #[error("{0:?} overlaps {1:?}")]
+    OverlappingSubplots(layout::FractionalArea, layout::FractionalArea),
+    /// Returned in strict mode when content is drawn outside of the figure.
+    #[error("content is out of bounds: {0}")]
+    OutOfBounds(String),
+    /// Returned when a frame does not match the size of an animation.
+    #[error(
+        "frame size of {}x{} does not match animation size of {}x{}",
+        .found.0, .found.1, .expected.0, .expected.1,
+    )]
+    InvalidFrameSize { expected: (u32, u32), found: (u32, u32) },
+    /// Returned when reading or writing a file fails.
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
     /// Returned when the drawing backend returns an error.
     #[error(transparent)]
     DrawError(#[from] draw::DrawError)
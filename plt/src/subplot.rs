@@ -1,6 +1,22 @@
-use crate::{Color, FontName, PltError};
+use crate::{Alignment, Color, Colormap, Font, FontName, Normalization, Point, PltError};
 
-use std::{array, fmt::{self, Formatter}, f64, iter};
+use std::{array, fmt::{self, Formatter}, f64, iter, sync};
+use std::collections::BTreeMap;
+#[cfg(feature = "csv")]
+use std::{fs, io, path};
+
+#[cfg(feature = "csv")]
+mod csv;
+mod density;
+#[cfg(feature = "time")]
+mod time;
+
+/// The default width of bars, in x-data units, so bars spaced 1.0 apart have gaps between them.
+const BAR_WIDTH: f64 = 0.8;
+/// The default fraction of the data range added to each end of automatic axis limits.
+const AUTOSCALE_MARGIN: f64 = 0.05;
+/// The default distance automatic limits extend past data without any range.
+const AUTOSCALE_FLAT_MARGIN: f64 = 1.0;
 
 /// The object that represents a whole subplot and is used to draw plotted data.
 #[derive(Clone, Debug)]
@@ -9,11 +25,20 @@ pub struct Subplot<'a> {
     pub(crate) plot_order: Vec<PlotType>,
     pub(crate) plot_infos: Vec<PlotInfo<'a>>,
     pub(crate) fill_infos: Vec<FillInfo<'a>>,
+    pub(crate) bar_infos: Vec<BarInfo<'a>>,
+    pub(crate) stem_infos: Vec<StemInfo<'a>>,
+    pub(crate) heatmap_infos: Vec<HeatmapInfo>,
+    pub(crate) reference_infos: Vec<ReferenceInfo>,
+    pub(crate) annotations: Vec<AnnotationInfo>,
     pub(crate) title: String,
     pub(crate) xaxis: AxisBuf,
     pub(crate) yaxis: AxisBuf,
     pub(crate) secondary_xaxis: AxisBuf,
     pub(crate) secondary_yaxis: AxisBuf,
+    pub(crate) grid_axes: Axes,
+    pub(crate) legend: Option<LegendPosition>,
+    pub(crate) colorbar: Option<(Colormap, (f64, f64))>,
+    pub(crate) equal_aspect: bool,
 }
 impl<'a> Subplot<'a> {
     /// Returns a builder with default settings for constructing a subplot.
@@ -60,6 +85,73 @@ impl<'a> Subplot<'a> {
         plotter.plot(xs, ys)
     }
 
+    /// Plots X, Y data on this subplot with default plot formatting,
+    /// returning a handle to the series for changing it later.
+    /// Shortcut for calling `.plotter().plot_handle()` on a [`Subplot`].
+    pub fn plot_handle<Xs, Ys, Fx, Fy>(
+        &mut self,
+        xs: Xs,
+        ys: Ys,
+    ) -> Result<SeriesHandle, PltError>
+    where
+        Fx: IntoF64,
+        Fy: IntoF64,
+        Xs: IntoIterator<Item=Fx>,
+        Ys: IntoIterator<Item=Fy>,
+        <Xs as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+        <Ys as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+    {
+        let plotter = Plotter {
+            subplot: self,
+            desc: PlotDescriptor::default(),
+        };
+
+        plotter.plot_handle(xs, ys)
+    }
+
+    /// Plots X, Y data against the secondary y-axis, which gets its own limits, ticks, and labels.
+    /// Shortcut for calling `.plotter().use_secondary_yaxis().plot()` on a [`Subplot`].
+    ///
+    /// Grid lines are only drawn once per side, by the axis set with [`SubplotBuilder::grid_axis`].
+    pub fn plot_twin_y<Xs, Ys, Fx, Fy>(
+        &mut self,
+        xs: Xs,
+        ys: Ys,
+    ) -> Result<(), PltError>
+    where
+        Fx: IntoF64,
+        Fy: IntoF64,
+        Xs: IntoIterator<Item=Fx>,
+        Ys: IntoIterator<Item=Fy>,
+        <Xs as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+        <Ys as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+    {
+        let plotter = Plotter {
+            subplot: self,
+            desc: PlotDescriptor::default(),
+        };
+
+        plotter.use_secondary_yaxis().plot(xs, ys)
+    }
+
+    /// Reads X, Y data from two columns of a CSV file and plots it with default formatting.
+    ///
+    /// Columns are indexed from 0, and the first row is skipped when `header` is true.
+    /// Files without any data rows and cells that are not numbers return [`PltError::InvalidData`].
+    #[cfg(feature = "csv")]
+    pub fn plot_csv<P: AsRef<path::Path>>(
+        &mut self,
+        path: P,
+        x_col: usize,
+        y_col: usize,
+        header: bool,
+    ) -> Result<(), PltError> {
+        let file = fs::File::open(path)?;
+        let (xs, ys) = csv::read_columns(io::BufReader::new(file), x_col, y_col, header)?;
+
+        self.plot(xs, ys)
+    }
+
     /// Plots step plot data on this subplot with default plot formatting.
     /// Shortcut for calling `.plotter().step()` on a [`Subplot`].
     pub fn step<Xs, Ys, Fx, Fy>(
@@ -83,6 +175,89 @@ impl<'a> Subplot<'a> {
         plotter.step(steps, ys)
     }
 
+    /// Draws a vertical bar at each x-value, with the y-values as bar heights and default formatting.
+    /// Shortcut for calling `.plotter().bar()` on a [`Subplot`].
+    pub fn bar<Xs, Ys, Fx, Fy>(
+        &mut self,
+        xs: Xs,
+        heights: Ys,
+    ) -> Result<(), PltError>
+    where
+        Fx: IntoF64,
+        Fy: IntoF64,
+        Xs: IntoIterator<Item=Fx>,
+        Ys: IntoIterator<Item=Fy>,
+        <Xs as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+        <Ys as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+    {
+        let plotter = Plotter {
+            subplot: self,
+            desc: PlotDescriptor::default(),
+        };
+
+        plotter.bar(xs, heights)
+    }
+
+    /// Draws a stem from the baseline to each data point, with default plot formatting.
+    /// Shortcut for calling `.plotter().stem()` on a [`Subplot`].
+    pub fn stem<Xs, Ys, Fx, Fy>(
+        &mut self,
+        xs: Xs,
+        ys: Ys,
+    ) -> Result<(), PltError>
+    where
+        Fx: IntoF64,
+        Fy: IntoF64,
+        Xs: IntoIterator<Item=Fx>,
+        Ys: IntoIterator<Item=Fy>,
+        <Xs as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+        <Ys as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+    {
+        let plotter = Plotter {
+            subplot: self,
+            desc: PlotDescriptor::default(),
+        };
+
+        plotter.stem(xs, ys)
+    }
+
+    /// Plots markers colored by values on this subplot, with default plot formatting.
+    /// Shortcut for calling `.plotter().scatter_c()` on a [`Subplot`].
+    pub fn scatter_c<Xs, Ys, Vs, Fx, Fy, Fv>(
+        &mut self,
+        xs: Xs,
+        ys: Ys,
+        values: Vs,
+    ) -> Result<(), PltError>
+    where
+        Fx: IntoF64,
+        Fy: IntoF64,
+        Fv: IntoF64,
+        Xs: IntoIterator<Item=Fx>,
+        Ys: IntoIterator<Item=Fy>,
+        Vs: IntoIterator<Item=Fv>,
+        <Xs as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+        <Ys as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+    {
+        let plotter = Plotter {
+            subplot: self,
+            desc: PlotDescriptor::default(),
+        };
+
+        plotter.scatter_c(xs, ys, values)
+    }
+
+    /// Draws a grid of values as cells colored by a colormap, with default plot formatting.
+    /// Shortcut for calling `.plotter().heatmap()` on a [`Subplot`].
+    pub fn heatmap(&mut self, data: ndarray::Array2<f64>) -> Result<(), PltError> {
+        let plotter = Plotter {
+            subplot: self,
+            desc: PlotDescriptor::default(),
+        };
+
+        plotter.heatmap(data)
+    }
+
     /// Fills an area between two curves on the subplot with default formatting.
     /// Shortcut for calling `.filler().fill_between()` on a [`Subplot`].
     pub fn fill_between<Xs, Y1s, Y2s, Fx, Fy1, Fy2>(
@@ -113,10 +288,530 @@ impl<'a> Subplot<'a> {
         filler.fill_between(xs, y1s, y2s)
     }
 
+    /// Fills the area under step data down to a baseline of 0.0, such as for a filled histogram.
+    /// Shortcut for calling `.filler().fill_step()` on a [`Subplot`].
+    pub fn fill_step<Xs, Ys, Fx, Fy>(
+        &mut self,
+        steps: Xs,
+        ys: Ys,
+    ) -> Result<(), PltError>
+    where
+        Fx: IntoF64,
+        Fy: IntoF64,
+        Xs: IntoIterator<Item=Fx>,
+        Ys: IntoIterator<Item=Fy>,
+        <Xs as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+        <Ys as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+    {
+        let filler = Filler {
+            subplot: self,
+            desc: FillDescriptor::default(),
+        };
+
+        filler.fill_step(steps, ys)
+    }
+
+    /// Counts data in bins and fills the histogram, with default fill formatting.
+    /// Shortcut for calling `.filler().hist()` on a [`Subplot`].
+    pub fn hist<Ds, F, B>(&mut self, data: Ds, bins: B) -> Result<(), PltError>
+    where
+        F: IntoF64,
+        Ds: IntoIterator<Item=F>,
+        B: Into<Bins>,
+    {
+        let filler = Filler {
+            subplot: self,
+            desc: FillDescriptor::default(),
+        };
+
+        filler.hist(data, bins)
+    }
+
+    /// Plots the empirical cumulative distribution function of data, with default plot formatting.
+    ///
+    /// The step curve rises from 0.0 at the smallest value to 1.0 at the largest,
+    /// by the fraction of values at each value.
+    pub fn ecdf<Ds, F>(&mut self, data: Ds) -> Result<(), PltError>
+    where
+        F: IntoF64,
+        Ds: IntoIterator<Item=F>,
+    {
+        let mut data = data.into_iter().map(|f| f.f64()).collect::<Vec<_>>();
+        if data.is_empty() {
+            return Err(PltError::InvalidData("ecdf data is empty".to_owned()));
+        } else if data.iter().any(|x| x.is_nan()) {
+            return Err(PltError::InvalidData("ecdf data has NaN value".to_owned()));
+        }
+        data.sort_by(f64::total_cmp);
+
+        // repeat the ends, so the curve steps up from 0.0 and up to 1.0
+        let n = data.len();
+        let edges = iter::once(data[0])
+            .chain(data.iter().copied())
+            .chain(iter::once(data[n - 1]))
+            .collect::<Vec<_>>();
+        let fractions = (0..=n).map(|i| i as f64 / n as f64).collect::<Vec<_>>();
+
+        self.step(edges, fractions)
+    }
+
+    /// Plots a smooth estimate of the probability density of data, with default plot formatting.
+    ///
+    /// The estimate sums Gaussian kernels centered on each value, so it integrates to about 1.0.
+    /// The `bandwidth` is the standard deviation of the kernels, which defaults to Silverman's rule of thumb.
+    pub fn kde<Ds, F>(&mut self, data: Ds, bandwidth: Option<f64>) -> Result<(), PltError>
+    where
+        F: IntoF64,
+        Ds: IntoIterator<Item=F>,
+    {
+        let data = data.into_iter().map(|f| f.f64()).collect::<Vec<_>>();
+        let (xs, ys) = density::gaussian_kde(&data, bandwidth)?;
+
+        self.plot(xs, ys)
+    }
+
+    /// Plots the quantiles of data against the quantiles of a standard normal distribution,
+    /// for checking whether data is normally distributed.
+    ///
+    /// The data is drawn as markers, with tied values sharing a single point.
+    /// A dashed reference line shows a normal distribution with the mean and standard deviation of the data,
+    /// so normally distributed data falls along it.
+    pub fn qqplot<Ds, F>(&mut self, data: Ds) -> Result<(), PltError>
+    where
+        F: IntoF64,
+        Ds: IntoIterator<Item=F>,
+    {
+        let data = data.into_iter().map(|f| f.f64()).collect::<Vec<_>>();
+        let pairs = density::normal_qq(&data)?;
+
+        let ends = [pairs.theoretical[0], pairs.theoretical[pairs.theoretical.len() - 1]];
+        let line_color = self.format.line_color;
+
+        self.plotter()
+            .line(None)
+            .marker(Some(MarkerStyle::Circle))
+            .plot(pairs.theoretical, pairs.sample)?;
+
+        self.plotter()
+            .line(Some(LineStyle::Dashed))
+            .line_color(line_color)
+            .plot(ends, ends.map(|x| pairs.location + pairs.scale * x))
+    }
+
+    /// Draws a bar for each key of a map, in key order, with the values as bar heights.
+    ///
+    /// The keys label the x-axis ticks, which are placed at the bar centers, 0.0, 1.0, 2.0, and so on.
+    pub fn bar_map<K: AsRef<str>>(&mut self, map: &BTreeMap<K, f64>) -> Result<(), PltError> {
+        if map.is_empty() {
+            return Err(PltError::InvalidData("map is empty".to_owned()));
+        }
+
+        let xs = (0..map.len()).map(|i| i as f64).collect::<Vec<_>>();
+        let heights = map.values().copied().collect::<Vec<_>>();
+
+        self.bar(xs, heights)?;
+
+        // label the bars by key
+        self.label_categories(map.keys());
+
+        Ok(())
+    }
+
+    /// Draws a group of bars for each category, with a bar for each series side by side.
+    ///
+    /// Each series is a label and a value for each category, and takes the next color of the color cycle,
+    /// with a legend entry unless its label is empty.
+    /// The categories label the x-axis ticks, which are placed at the group centers, 0.0, 1.0, 2.0, and so on.
+    ///
+    /// ```
+    /// let mut subplot = plt::Subplot::builder().legend(true).build();
+    ///
+    /// subplot.grouped_bar(
+    ///     &["north", "south", "east"],
+    ///     vec![("2023", vec![3.0, 5.0, 2.0]), ("2024", vec![4.0, 4.5, 3.0])],
+    /// ).unwrap();
+    ///
+    /// // each series needs a value for each category
+    /// assert!(subplot.grouped_bar(&["north"], vec![("2025", vec![1.0, 2.0])]).is_err());
+    /// ```
+    pub fn grouped_bar<C, L, Vs, F>(&mut self, categories: &[C], series: Vec<(L, Vs)>) -> Result<(), PltError>
+    where
+        C: AsRef<str>,
+        L: AsRef<str>,
+        F: IntoF64,
+        Vs: IntoIterator<Item=F>,
+    {
+        let series = category_series(categories, series)?;
+
+        // split the default bar width between the bars of each group
+        let width = BAR_WIDTH / series.len() as f64;
+        for (i, (label, values)) in series.into_iter().enumerate() {
+            let offset = (i as f64 + 0.5) * width - 0.5 * BAR_WIDTH;
+            let xs = (0..categories.len()).map(|x| x as f64 + offset).collect::<Vec<_>>();
+
+            self.plotter()
+                .label(label)
+                .bar_width(width)
+                .bar(xs, values)?;
+        }
+
+        self.label_categories(categories);
+
+        Ok(())
+    }
+
+    /// Draws a stack of bars for each category, with a bar for each series stacked on the series before it.
+    ///
+    /// Each series is a label and a value for each category, and takes the next color of the color cycle,
+    /// with a legend entry unless its label is empty.
+    /// Positive values stack upward from 0.0, and negative values stack downward.
+    /// The categories label the x-axis ticks, which are placed at the stack centers, 0.0, 1.0, 2.0, and so on.
+    ///
+    /// ```
+    /// let mut subplot = plt::Subplot::builder().legend(true).build();
+    ///
+    /// subplot.stacked_bar(
+    ///     &["north", "south", "east"],
+    ///     vec![("coal", vec![3.0, 5.0, 2.0]), ("solar", vec![4.0, 4.5, 3.0])],
+    /// ).unwrap();
+    /// ```
+    pub fn stacked_bar<C, L, Vs, F>(&mut self, categories: &[C], series: Vec<(L, Vs)>) -> Result<(), PltError>
+    where
+        C: AsRef<str>,
+        L: AsRef<str>,
+        F: IntoF64,
+        Vs: IntoIterator<Item=F>,
+    {
+        let series = category_series(categories, series)?;
+
+        // the tops of the positive and negative stacks in each category
+        let mut tops = vec![(0.0, 0.0); categories.len()];
+        for (label, values) in series {
+            let bases = iter::zip(&mut tops, &values)
+                .map(|((positive_top, negative_top), &value)| {
+                    let top = if value < 0.0 { negative_top } else { positive_top };
+                    let base = *top;
+                    *top += value;
+
+                    base
+                })
+                .collect::<Vec<_>>();
+            let xs = (0..categories.len()).map(|x| x as f64).collect::<Vec<_>>();
+
+            let mut plotter = self.plotter().label(label);
+            plotter.desc.bar_bases = Some(bases);
+            plotter.bar(xs, values)?;
+        }
+
+        self.label_categories(categories);
+
+        Ok(())
+    }
+
+    /// Draws a horizontal line across the plot area at a y-value of the primary y-axis,
+    /// returning a [`ReferenceLine`] for changing how the line is drawn.
+    ///
+    /// Reference lines are drawn in order with plotted data, and count as a series when indexing series,
+    /// but they do not change automatic axis limits, take colors from the color cycle, or appear in the legend.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let mut subplot = Subplot::builder().build();
+    /// subplot.plot([0.0, 1.0, 2.0], [0.0, 4.0, 1.0]).unwrap();
+    ///
+    /// subplot.axhline(3.0).line_style(LineStyle::Dashed);
+    /// subplot.axvline(1.5).color(Color::BLACK).width(1);
+    /// subplot.axspan(Orientation::Vertical, (0.25, 0.75));
+    ///
+    /// // reference lines do not change the axis limits
+    /// subplot.axhline(10.0);
+    /// ```
+    pub fn axhline<'b>(&'b mut self, y: f64) -> ReferenceLine<'b> {
+        self.reference_desc(ReferenceKind::Line(Orientation::Horizontal, y));
+
+        ReferenceLine { info: self.reference_infos.last_mut().unwrap() }
+    }
+
+    /// Draws a vertical line across the plot area at an x-value of the primary x-axis,
+    /// returning a [`ReferenceLine`] for changing how the line is drawn.
+    /// See [`Subplot::axhline`].
+    pub fn axvline<'b>(&'b mut self, x: f64) -> ReferenceLine<'b> {
+        self.reference_desc(ReferenceKind::Line(Orientation::Vertical, x));
+
+        ReferenceLine { info: self.reference_infos.last_mut().unwrap() }
+    }
+
+    /// Fills a band across the plot area between two values of the primary axes,
+    /// returning a [`ReferenceSpan`] for changing its color.
+    ///
+    /// Horizontal bands lie between two y-values, and vertical bands between two x-values.
+    /// Like reference lines, bands do not change automatic axis limits. See [`Subplot::axhline`].
+    pub fn axspan<'b>(&'b mut self, orientation: Orientation, (min, max): (f64, f64)) -> ReferenceSpan<'b> {
+        self.reference_desc(ReferenceKind::Span(orientation, (min, max)));
+
+        ReferenceSpan { info: self.reference_infos.last_mut().unwrap() }
+    }
+
+    /// Labels a point in data coordinates of the primary axes with text,
+    /// returning an [`Annotation`] for changing how the text is drawn.
+    ///
+    /// Annotations are drawn over the data, and clipped to the plot area.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let mut subplot = Subplot::builder().build();
+    /// subplot.plot([0.0, 1.0, 2.0], [0.0, 4.0, 1.0]).unwrap();
+    ///
+    /// subplot.annotate("peak", (1.0, 4.0))
+    ///     .alignment(Alignment::Bottom)
+    ///     .offset_pixels((0.0, 5.0));
+    /// subplot.annotate("rising", (0.5, 2.0))
+    ///     .color(Color::BLACK)
+    ///     .font_size(14.0)
+    ///     .rotation(60.0);
+    /// subplot.annotate("minimum", (1.5, 0.5))
+    ///     .arrow_to((2.0, 1.0))
+    ///     .arrow_color(Color::BLACK);
+    /// ```
+    pub fn annotate<'b, S: AsRef<str>>(&'b mut self, text: S, (x, y): (f64, f64)) -> Annotation<'b> {
+        self.annotations.push(AnnotationInfo {
+            text: text.as_ref().to_owned(),
+            position: (x, y),
+            color_override: None,
+            font_size: None,
+            rotation: 0.0,
+            alignment: Alignment::Center,
+            offset: (0.0, 0.0),
+            arrow_target: None,
+            arrow_color: None,
+        });
+
+        Annotation { info: self.annotations.last_mut().unwrap() }
+    }
+
+    /// Returns whether a series is drawn.
+    pub fn series_visible(&self, series: SeriesHandle) -> Result<bool, PltError> {
+        let (plot_type, nth) = self.series_index(series)?;
+
+        Ok(match plot_type {
+            PlotType::Series => self.plot_infos[nth].visible,
+            PlotType::Fill => self.fill_infos[nth].visible,
+            PlotType::Bar => self.bar_infos[nth].visible,
+            PlotType::Stem => self.stem_infos[nth].visible,
+            PlotType::Heatmap => self.heatmap_infos[nth].visible,
+            PlotType::Reference => self.reference_infos[nth].visible,
+        })
+    }
+
+    /// Sets whether a series is drawn.
+    ///
+    /// Hidden series keep their colors, legend entries, and effect on axis limits,
+    /// so showing and hiding series leaves the rest of the subplot unchanged.
+    pub fn set_series_visible(&mut self, series: SeriesHandle, visible: bool) -> Result<(), PltError> {
+        let (plot_type, nth) = self.series_index(series)?;
+
+        match plot_type {
+            PlotType::Series => self.plot_infos[nth].visible = visible,
+            PlotType::Fill => self.fill_infos[nth].visible = visible,
+            PlotType::Bar => self.bar_infos[nth].visible = visible,
+            PlotType::Stem => self.stem_infos[nth].visible = visible,
+            PlotType::Heatmap => self.heatmap_infos[nth].visible = visible,
+            PlotType::Reference => self.reference_infos[nth].visible = visible,
+        }
+
+        Ok(())
+    }
+
+    /// Sets the color of a series.
+    ///
+    /// This is the same as overriding the color when plotting, so series lines and markers both take the color.
+    /// Series with an override do not take a color from [`SubplotFormat::color_cycle`],
+    /// and `None` removes the override, so the series takes its color from the cycle again.
+    /// Heatmaps are colored by their colormap, so they cannot be given a color.
+    pub fn set_series_color(&mut self, series: SeriesHandle, color: Option<Color>) -> Result<(), PltError> {
+        let (plot_type, nth) = self.series_index(series)?;
+
+        match plot_type {
+            PlotType::Series => {
+                let info = &mut self.plot_infos[nth];
+                if let Some(line) = &mut info.line {
+                    line.color_override = color;
+                }
+                if let Some(marker) = &mut info.marker {
+                    marker.color_override = color;
+                }
+            },
+            PlotType::Fill => self.fill_infos[nth].color_override = color,
+            PlotType::Bar => self.bar_infos[nth].color_override = color,
+            PlotType::Stem => {
+                let info = &mut self.stem_infos[nth];
+                info.line.color_override = color;
+                info.marker.color_override = color;
+            },
+            PlotType::Reference => self.reference_infos[nth].line.color_override = color,
+            PlotType::Heatmap => {
+                return Err(PltError::InvalidData(format!(
+                    "series {} is a heatmap, which is colored by its colormap", series.index(),
+                )));
+            },
+        }
+
+        Ok(())
+    }
+
+    /// Sets the style of the line of a series, or the edge of a fill.
+    pub fn set_series_line_style(&mut self, series: SeriesHandle, style: LineStyle) -> Result<(), PltError> {
+        let (plot_type, nth) = self.series_index(series)?;
+
+        let line = match plot_type {
+            PlotType::Series => self.plot_infos[nth].line.as_mut(),
+            PlotType::Fill => self.fill_infos[nth].edge.as_mut(),
+            PlotType::Stem => Some(&mut self.stem_infos[nth].line),
+            PlotType::Reference => match self.reference_infos[nth].kind {
+                ReferenceKind::Line(..) => Some(&mut self.reference_infos[nth].line),
+                ReferenceKind::Span(..) => None,
+            },
+            PlotType::Bar | PlotType::Heatmap => None,
+        };
+
+        if let Some(line) = line {
+            line.style = style;
+
+            Ok(())
+        } else {
+            Err(PltError::InvalidData(format!("series {} is not drawn with a line", series.index())))
+        }
+    }
+
+    /// Sets the label of a series shown in the legend.
+    /// Series with an empty label are left out of the legend.
+    pub fn set_series_label(&mut self, series: SeriesHandle, label: &str) -> Result<(), PltError> {
+        let (plot_type, nth) = self.series_index(series)?;
+
+        match plot_type {
+            PlotType::Series => self.plot_infos[nth].label = label.to_owned(),
+            PlotType::Fill => self.fill_infos[nth].label = label.to_owned(),
+            PlotType::Bar => self.bar_infos[nth].label = label.to_owned(),
+            PlotType::Stem => self.stem_infos[nth].label = label.to_owned(),
+            PlotType::Heatmap => {
+                return Err(PltError::InvalidData(format!(
+                    "series {} is a heatmap, which is not shown in the legend", series.index(),
+                )));
+            },
+            PlotType::Reference => {
+                return Err(PltError::InvalidData(format!(
+                    "series {} is a reference line, which is not shown in the legend", series.index(),
+                )));
+            },
+        }
+
+        Ok(())
+    }
+
+    /// Recomputes automatic limits from scratch, so they fit the data currently plotted.
+    ///
+    /// Limits only grow as data is plotted, so this tightens them after data is changed.
+    /// Manual limits are kept.
+    pub fn recompute_limits(&mut self) {
+        for placement in AxisType::iter() {
+            self.axis_mut(placement).reset_limits();
+        }
+
+        let mut extents = Vec::with_capacity(self.plot_order.len());
+        extents.extend(self.plot_infos.iter().map(|info| (info.xaxis, info.yaxis, info.extents())));
+        extents.extend(self.fill_infos.iter().map(|info| (info.xaxis, info.yaxis, info.extents())));
+        extents.extend(self.bar_infos.iter().map(|info| (info.xaxis, info.yaxis, info.extents())));
+        extents.extend(self.stem_infos.iter().map(|info| (info.xaxis, info.yaxis, info.extents())));
+        extents.extend(self.heatmap_infos.iter().map(|info| (info.xaxis, info.yaxis, info.extents())));
+
+        for (xaxis, yaxis, (xextent, yextent)) in extents {
+            self.axis_mut(xaxis).fit_limits(xextent.0, xextent.1);
+            self.axis_mut(yaxis).fit_limits(yextent.0, yextent.1);
+        }
+    }
+
+    /// Removes all plotted data, keeping the format and axes, so the subplot can be reused.
+    ///
+    /// Automatic limits are reset, so they fit the next data plotted, while manual limits are kept.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let mut sp = Subplot::builder()
+    ///     .limits(Axes::Y, Limits::Manual { min: 0.0, max: 1.0 })
+    ///     .build();
+    ///
+    /// // redraw with new data each frame
+    /// for frame in [3, 2, 1] {
+    ///     sp.clear_data();
+    ///     sp.plot([0.0, frame as f64], [0.0, 1.0]).unwrap();
+    ///
+    ///     let (xmin, xmax) = sp.limits(Axes::X).unwrap();
+    ///     assert!(xmin < 0.0 && xmax > frame as f64 && xmax < frame as f64 + 1.0);
+    ///     assert_eq!(sp.limits(Axes::Y), Some((0.0, 1.0)));
+    /// }
+    /// ```
+    pub fn clear_data(&mut self) {
+        self.plot_order.clear();
+        self.plot_infos.clear();
+        self.fill_infos.clear();
+        self.bar_infos.clear();
+        self.stem_infos.clear();
+        self.heatmap_infos.clear();
+        self.reference_infos.clear();
+        self.annotations.clear();
+
+        for placement in AxisType::iter() {
+            self.axis_mut(placement).reset_limits();
+        }
+    }
+
     /// Returns the format of this plot.
     pub fn format(&self) -> &SubplotFormat {
         &self.format
     }
+
+    /// Returns the limits of an axis, as they would be drawn.
+    ///
+    /// Automatic limits are computed as data is plotted, including margins, and manual limits are returned as set.
+    /// An axis without limits of its own takes those of the opposite axis on its side.
+    /// Limits are ordered from the left or bottom of the plot area, so inverted axes return their maximum first.
+    ///
+    /// Returns `None` if no data has been plotted on either axis on the side, or if `axes` names more than one axis.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let mut sp = Subplot::builder()
+    ///     .limits(Axes::Y, Limits::Manual { min: -1.0, max: 1.0 })
+    ///     .build();
+    /// assert_eq!(sp.limits(Axes::X), None);
+    /// assert_eq!(sp.limits(Axes::Y), Some((-1.0, 1.0)));
+    ///
+    /// sp.plot([0.0, 10.0], [0.0, 0.5]).unwrap();
+    /// assert_eq!(sp.limits(Axes::X), Some((-0.5, 10.5)));
+    /// assert_eq!(sp.limits(Axes::SecondaryX), Some((-0.5, 10.5)));
+    /// assert_eq!(sp.limits(Axes::BothX), None);
+    /// ```
+    pub fn limits(&self, axes: Axes) -> Option<(f64, f64)> {
+        let (axis, opposite_axis) = match axes {
+            Axes::X => (&self.xaxis, &self.secondary_xaxis),
+            Axes::Y => (&self.yaxis, &self.secondary_yaxis),
+            Axes::SecondaryX => (&self.secondary_xaxis, &self.xaxis),
+            Axes::SecondaryY => (&self.secondary_yaxis, &self.yaxis),
+            _ => return None,
+        };
+
+        let (min, max) = axis.limits.or(opposite_axis.limits)?;
+
+        if axis.inverted {
+            Some((max, min))
+        } else {
+            Some((min, max))
+        }
+    }
 }
 impl<'a> Subplot<'a> {
     /// Internal constructor.
@@ -126,15 +821,120 @@ impl<'a> Subplot<'a> {
             plot_order: vec![],
             plot_infos: vec![],
             fill_infos: vec![],
+            bar_infos: vec![],
+            stem_infos: vec![],
+            heatmap_infos: vec![],
+            reference_infos: vec![],
+            annotations: vec![],
             title: desc.title.to_string(),
             xaxis: desc.xaxis.to_buf(),
             yaxis: desc.yaxis.to_buf(),
             secondary_xaxis: desc.secondary_xaxis.to_buf(),
             secondary_yaxis: desc.secondary_yaxis.to_buf(),
+            grid_axes: desc.grid_axes,
+            legend: desc.legend,
+            colorbar: desc.colorbar,
+            equal_aspect: desc.equal_aspect,
+        }
+    }
+
+    /// Each plotted dataset as its position in [`Subplot::plot_order`], its type,
+    /// and its index among datasets of that type, sorted by z-order.
+    /// The sort is stable, so datasets with equal z-order are drawn in the order they were added.
+    pub(crate) fn draw_order(&self) -> Vec<(usize, PlotType, usize)> {
+        let mut counts = [0; 6];
+        let mut order = self.plot_order.iter()
+            .enumerate()
+            .map(|(index, &plot_type)| {
+                let count = &mut counts[plot_type as usize];
+                let nth = *count;
+                *count += 1;
+
+                (index, plot_type, nth)
+            })
+            .collect::<Vec<_>>();
+
+        order.sort_by_key(|&(_, plot_type, nth)| match plot_type {
+            PlotType::Series => self.plot_infos[nth].zorder,
+            PlotType::Fill => self.fill_infos[nth].zorder,
+            PlotType::Bar => self.bar_infos[nth].zorder,
+            PlotType::Stem => self.stem_infos[nth].zorder,
+            PlotType::Heatmap => self.heatmap_infos[nth].zorder,
+            PlotType::Reference => self.reference_infos[nth].zorder,
+        });
+
+        order
+    }
+
+    /// The span of data on an axis with automatic limits,
+    /// or `None` if the axis has manual limits or no data.
+    pub(crate) fn auto_span(&self, placement: AxisType) -> Option<(f64, f64)> {
+        if self.manual_limits(placement) {
+            None
+        } else {
+            self.axis(placement).span
         }
     }
+
+    /// Whether an axis has manual limits, which are kept when sharing limits in a layout.
+    pub(crate) fn manual_limits(&self, placement: AxisType) -> bool {
+        matches!(self.axis(placement).limit_policy, Limits::Manual { .. })
+    }
+
+    /// Expands the span of an axis with automatic limits to cover a span shared with other subplots.
+    pub(crate) fn share_span(&mut self, placement: AxisType, (min, max): (f64, f64)) {
+        self.axis_mut(placement).fit_limits(min, max);
+    }
+
+    /// Removes the major and minor tick labels of an axis.
+    pub(crate) fn hide_tick_labels(&mut self, placement: AxisType) {
+        let axis = self.axis_mut(placement);
+        axis.major_tick_labels = TickLabels::None;
+        axis.minor_tick_labels = TickLabels::None;
+    }
 }
 impl<'a> Subplot<'a> {
+    /// Labels the x-axis ticks by category, with ticks at 0.0, 1.0, 2.0, and so on.
+    fn label_categories<S: AsRef<str>>(&mut self, categories: impl IntoIterator<Item=S>) {
+        let labels = categories.into_iter().map(|category| category.as_ref().to_owned()).collect::<Vec<_>>();
+
+        self.xaxis.major_tick_marks = TickSpacing::Manual((0..labels.len()).map(|i| i as f64).collect());
+        self.xaxis.major_tick_labels = TickLabels::Manual(labels);
+        self.xaxis.minor_tick_marks = TickSpacing::None;
+    }
+
+    /// Returns the axis at a placement.
+    fn axis(&self, placement: AxisType) -> &AxisBuf {
+        match placement {
+            AxisType::X => &self.xaxis,
+            AxisType::Y => &self.yaxis,
+            AxisType::SecondaryX => &self.secondary_xaxis,
+            AxisType::SecondaryY => &self.secondary_yaxis,
+        }
+    }
+
+    /// Returns the axis at a placement, to be changed.
+    fn axis_mut(&mut self, placement: AxisType) -> &mut AxisBuf {
+        match placement {
+            AxisType::X => &mut self.xaxis,
+            AxisType::Y => &mut self.yaxis,
+            AxisType::SecondaryX => &mut self.secondary_xaxis,
+            AxisType::SecondaryY => &mut self.secondary_yaxis,
+        }
+    }
+
+    /// Finds the type of a series, and its index among series of that type.
+    fn series_index(&self, series: SeriesHandle) -> Result<(PlotType, usize), PltError> {
+        let index = series.index();
+        let plot_type = *self.plot_order.get(index)
+            .ok_or(PltError::InvalidSeries { index, nseries: self.plot_order.len() })?;
+        let nth = self.plot_order[..index].iter()
+            .filter(|&&other| other == plot_type)
+            .count();
+
+        Ok((plot_type, nth))
+    }
+
     /// Internal plot setup function.
     fn plot_desc<D: SeriesData + Clone + 'a>(
         &mut self,
@@ -152,140 +952,160 @@ impl<'a> Subplot<'a> {
             None
         };
 
-        let xaxis = match desc.xaxis {
-            AxisType::X => &mut self.xaxis,
-            AxisType::Y => &mut self.yaxis,
-            AxisType::SecondaryX => &mut self.secondary_xaxis,
-            AxisType::SecondaryY => &mut self.secondary_yaxis,
-        };
-        match xaxis.limit_policy {
-            Limits::Auto => {
-                // span
-                xaxis.span = if let Some((xmin, xmax)) = xaxis.span {
-                    Some((f64::min(xmin, data.xmin()), f64::max(xmax, data.xmax())))
-                } else {
-                    Some((data.xmin(), data.xmax()))
-                };
-
-                // limits
-                let (xmin, xmax) = xaxis.span.unwrap();
-                let extent = xmax - xmin;
-                xaxis.limits = if extent > 0.0 {
-                    Some((xmin - 0.05 * extent, xmax + 0.05 * extent))
-                } else {
-                    Some((xmin - 1.0, xmax + 1.0))
-                };
-            },
-            Limits::Manual { min: _, max: _ } => {},
+        let errorbars = if desc.xerr.is_some() || desc.yerr.is_some() {
+            Some(ErrorBars {
+                xerr: desc.xerr,
+                yerr: desc.yerr,
+                cap_size: desc.errorbar_cap_size,
+                color_override: desc.errorbar_color,
+            })
+        } else {
+            None
         };
 
-        let yaxis = match desc.yaxis {
-            AxisType::X => &mut self.xaxis,
-            AxisType::Y => &mut self.yaxis,
-            AxisType::SecondaryX => &mut self.secondary_xaxis,
-            AxisType::SecondaryY => &mut self.secondary_yaxis,
+        #[cfg(feature = "jitter")]
+        let jitter = desc.jitter.map(|jitter| jitter.within_categories(&data));
+
+        let info = PlotInfo {
+            label: desc.label.to_string(),
+            data: Box::new(data),
+            line,
+            marker,
+            marker_colors: desc.marker_colors,
+            errorbars,
+            xaxis: desc.xaxis,
+            yaxis: desc.yaxis,
+            pixel_perfect: desc.pixel_perfect,
+            #[cfg(feature = "jitter")]
+            jitter,
+            zorder: desc.zorder,
+            visible: true,
         };
-        match yaxis.limit_policy {
-            Limits::Auto => {
-                // span
-                yaxis.span = if let Some((ymin, ymax)) = yaxis.span {
-                    Some((f64::min(ymin, data.ymin()), f64::max(ymax, data.ymax())))
-                } else {
-                    Some((data.ymin(), data.ymax()))
-                };
-
-                // limits
-                let (ymin, ymax) = yaxis.span.unwrap();
-                let extent = ymax - ymin;
-                yaxis.limits = if extent > 0.0 {
-                    Some((ymin - 0.05 * extent, ymax + 0.05 * extent))
-                } else {
-                    Some((ymin - 1.0, ymax + 1.0))
-                };
-            },
-            Limits::Manual { min: _, max: _ } => {},
+
+        let (xextent, yextent) = info.extents();
+        self.axis_mut(info.xaxis).fit_limits(xextent.0, xextent.1);
+        self.axis_mut(info.yaxis).fit_limits(yextent.0, yextent.1);
+
+        self.plot_infos.push(info);
+        self.plot_order.push(PlotType::Series);
+    }
+
+    /// Internal fill between setup function.
+    fn fill_between_desc<D: FillData + 'a>(
+        &mut self,
+        desc: FillDescriptor,
+        data: D,
+    ) {
+        let info = FillInfo {
+            label: desc.label.to_string(),
+            data: Box::new(data),
+            color_override: desc.color_override,
+            xaxis: desc.xaxis,
+            yaxis: desc.yaxis,
+            pixel_perfect: desc.pixel_perfect,
+            edge: desc.edge,
+            zorder: desc.zorder,
+            visible: true,
         };
 
-        self.plot_infos.push(PlotInfo {
-            label: desc.label.to_string(),
+        let (xextent, yextent) = info.extents();
+        self.axis_mut(info.xaxis).fit_limits(xextent.0, xextent.1);
+        self.axis_mut(info.yaxis).fit_limits(yextent.0, yextent.1);
+
+        self.fill_infos.push(info);
+        self.plot_order.push(PlotType::Fill);
+    }
+
+    /// Internal bar setup function.
+    fn bar_desc<D: SeriesData + 'a>(
+        &mut self,
+        desc: PlotDescriptor,
+        data: D,
+    ) {
+        let info = BarInfo {
+            label: desc.label,
             data: Box::new(data),
-            line,
-            marker,
+            width: desc.bar_width,
+            base: desc.bar_base,
+            bases: desc.bar_bases,
+            color_override: desc.bar_color,
             xaxis: desc.xaxis,
             yaxis: desc.yaxis,
-            pixel_perfect: desc.pixel_perfect,
-        });
-        self.plot_order.push(PlotType::Series);
+            zorder: desc.zorder,
+            visible: true,
+        };
+
+        let (xextent, yextent) = info.extents();
+        self.axis_mut(info.xaxis).fit_limits(xextent.0, xextent.1);
+        self.axis_mut(info.yaxis).fit_limits(yextent.0, yextent.1);
+
+        self.bar_infos.push(info);
+        self.plot_order.push(PlotType::Bar);
     }
 
-    /// Internal fill between setup function.
-    fn fill_between_desc<D: FillData + 'a>(
+    /// Internal stem setup function.
+    fn stem_desc<D: SeriesData + 'a>(
         &mut self,
-        desc: FillDescriptor,
+        desc: PlotDescriptor,
         data: D,
     ) {
-        let xaxis = match desc.xaxis {
-            AxisType::X => &mut self.xaxis,
-            AxisType::Y => &mut self.yaxis,
-            AxisType::SecondaryX => &mut self.secondary_xaxis,
-            AxisType::SecondaryY => &mut self.secondary_yaxis,
-        };
-        match xaxis.limit_policy {
-            Limits::Auto => {
-                // span
-                xaxis.span = if let Some((xmin, xmax)) = xaxis.span {
-                    Some((f64::min(xmin, data.xmin()), f64::max(xmax, data.xmax())))
-                } else {
-                    Some((data.xmin(), data.xmax()))
-                };
-
-                // limits
-                let (xmin, xmax) = xaxis.span.unwrap();
-                let extent = xmax - xmin;
-                xaxis.limits = if extent > 0.0 {
-                    Some((xmin - 0.05 * extent, xmax + 0.05 * extent))
-                } else {
-                    Some((xmin - 1.0, xmax + 1.0))
-                };
-            },
-            Limits::Manual { min: _, max: _ } => {},
+        let info = StemInfo {
+            label: desc.label,
+            data: Box::new(data),
+            line: desc.line_format,
+            marker: desc.marker_format,
+            baseline: desc.stem_baseline,
+            xaxis: desc.xaxis,
+            yaxis: desc.yaxis,
+            zorder: desc.zorder,
+            visible: true,
         };
 
-        let yaxis = match desc.yaxis {
-            AxisType::X => &mut self.xaxis,
-            AxisType::Y => &mut self.yaxis,
-            AxisType::SecondaryX => &mut self.secondary_xaxis,
-            AxisType::SecondaryY => &mut self.secondary_yaxis,
-        };
-        match yaxis.limit_policy {
-            Limits::Auto => {
-                // span
-                yaxis.span = if let Some((ymin, ymax)) = yaxis.span {
-                    Some((f64::min(ymin, data.ymin()), f64::max(ymax, data.ymax())))
-                } else {
-                    Some((data.ymin(), data.ymax()))
-                };
-
-                // limits
-                let (ymin, ymax) = yaxis.span.unwrap();
-                let extent = ymax - ymin;
-                yaxis.limits = if extent > 0.0 {
-                    Some((ymin - 0.05 * extent, ymax + 0.05 * extent))
-                } else {
-                    Some((ymin - 1.0, ymax + 1.0))
-                };
-            },
-            Limits::Manual { min: _, max: _ } => {},
-        };
+        let (xextent, yextent) = info.extents();
+        self.axis_mut(info.xaxis).fit_limits(xextent.0, xextent.1);
+        self.axis_mut(info.yaxis).fit_limits(yextent.0, yextent.1);
 
-        self.fill_infos.push(FillInfo {
-            label: desc.label.to_string(),
-            data: Box::new(data),
-            color_override: desc.color_override,
+        self.stem_infos.push(info);
+        self.plot_order.push(PlotType::Stem);
+    }
+
+    /// Internal heatmap setup function.
+    fn heatmap_desc(
+        &mut self,
+        desc: PlotDescriptor,
+        colors: ndarray::Array2<Color>,
+    ) {
+        let info = HeatmapInfo {
+            colors,
             xaxis: desc.xaxis,
             yaxis: desc.yaxis,
+            zorder: desc.zorder,
+            visible: true,
+        };
+
+        // the grid fills the plot area, without margins
+        let (xextent, yextent) = info.extents();
+        self.axis_mut(info.xaxis).margin = 0.0;
+        self.axis_mut(info.xaxis).fit_limits(xextent.0, xextent.1);
+        self.axis_mut(info.yaxis).margin = 0.0;
+        self.axis_mut(info.yaxis).fit_limits(yextent.0, yextent.1);
+
+        self.heatmap_infos.push(info);
+        self.plot_order.push(PlotType::Heatmap);
+    }
+
+    /// Internal reference line setup function.
+    fn reference_desc(&mut self, kind: ReferenceKind) {
+        self.reference_infos.push(ReferenceInfo {
+            kind,
+            line: Line {
+                width: self.format.line_width,
+                ..Default::default()
+            },
+            zorder: 0,
+            visible: true,
         });
-        self.plot_order.push(PlotType::Fill);
+        self.plot_order.push(PlotType::Reference);
     }
 }
 
@@ -330,6 +1150,11 @@ impl<'a> SubplotBuilder<'a> {
     pub fn ylabel(self, label: &'a str) -> Self {
         self.label(Axes::Y, label)
     }
+    /// Sets the secondary y-axis label.
+    /// Shortcut for calling `.label(Axes::SecondaryY, label)`.
+    pub fn secondary_ylabel(self, label: &'a str) -> Self {
+        self.label(Axes::SecondaryY, label)
+    }
 
     /// Sets axis limits.
     pub fn limits(mut self, axes: Axes, limits: Limits) -> Self {
@@ -354,6 +1179,34 @@ impl<'a> SubplotBuilder<'a> {
     pub fn ylimits(self, limits: Limits) -> Self {
         self.limits(Axes::Y, limits)
     }
+    /// Sets the secondary y-axis limits, such as for data plotted with [`Subplot::plot_twin_y`],
+    /// independently of the primary y-axis.
+    /// Shortcut for calling `.limits(Axes::SecondaryY, limits)`.
+    ///
+    /// An axis without manual limits or plotted data takes the limits of the opposite axis on its side,
+    /// but manual limits are always kept.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let mut subplot = Subplot::builder()
+    ///     .ylabel("temperature")
+    ///     .secondary_ylabel("pressure")
+    ///     .secondary_ylimits(Limits::Manual { min: 900.0, max: 1100.0 })
+    ///     .build();
+    /// subplot.plot([0.0, 1.0, 2.0], [10.0, 20.0, 15.0]).unwrap();
+    /// subplot.plot_twin_y([0.0, 1.0, 2.0], [1000.0, 1010.0, 990.0]).unwrap();
+    ///
+    /// assert_eq!(subplot.limits(Axes::Y), Some((9.5, 20.5)));
+    /// assert_eq!(subplot.limits(Axes::SecondaryY), Some((900.0, 1100.0)));
+    ///
+    /// let mut fig = <Figure>::default();
+    /// fig.set_layout(SingleLayout::new(subplot)).unwrap();
+    /// fig.draw_bitmap().unwrap();
+    /// ```
+    pub fn secondary_ylimits(self, limits: Limits) -> Self {
+        self.limits(Axes::SecondaryY, limits)
+    }
 
     /// Sets axis grid settings.
     pub fn grid(mut self, axes: Axes, grid: Grid) -> Self {
@@ -364,6 +1217,91 @@ impl<'a> SubplotBuilder<'a> {
 
         self
     }
+    /// Sets which axes draw grid lines when both axes on a side have grid lines on,
+    /// so the grid is not drawn twice.
+    /// Sides not covered by `axes` keep the primary axis.
+    /// Defaults to `Axes::BothPrimary`.
+    pub fn grid_axis(mut self, axes: Axes) -> Self {
+        self.desc.grid_axes = axes;
+
+        self
+    }
+
+    /// Sets whether to draw a legend, listing each plot with a label.
+    /// The legend is drawn in the [`LegendPosition::TopRight`] corner, unless otherwise positioned.
+    ///
+    /// Only the first entries that fit within the height of the plot area are listed.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let xs = [0.0, 1.0];
+    /// let ys = (0..40).map(|n| [n as f64, n as f64 + 1.0]).collect::<Vec<_>>();
+    /// let labels = (0..40).map(|n| format!("series {}", n)).collect::<Vec<_>>();
+    ///
+    /// for position in [LegendPosition::TopRight, LegendPosition::Outside] {
+    ///     let mut subplot = Subplot::builder().legend_position(position).build();
+    ///     for (ys, label) in ys.iter().zip(labels.iter()) {
+    ///         subplot.plotter().label(label).plot(xs, ys).unwrap();
+    ///     }
+    ///
+    ///     let size = FigSize { width: 4.0, height: 3.0 };
+    ///     let mut fig = <Figure>::new(&FigureFormat { size, ..Default::default() });
+    ///     fig.set_layout(SingleLayout::new(subplot)).unwrap();
+    ///     fig.draw_bitmap().unwrap();
+    /// }
+    /// ```
+    pub fn legend(mut self, legend: bool) -> Self {
+        self.desc.legend = if legend {
+            Some(self.desc.legend.unwrap_or_default())
+        } else {
+            None
+        };
+
+        self
+    }
+    /// Draws a legend at a position.
+    pub fn legend_position(mut self, position: LegendPosition) -> Self {
+        self.desc.legend = Some(position);
+
+        self
+    }
+
+    /// Draws a colorbar to the right of the plot area, showing a colormap over a range of values,
+    /// such as the range passed to [`Plotter::scatter_c`].
+    pub fn colorbar(mut self, colormap: Colormap, range: (f64, f64)) -> Self {
+        self.desc.colorbar = Some((colormap, range));
+
+        self
+    }
+
+    /// Sets whether a unit of data spans the same distance on both primary axes, so circles look circular.
+    ///
+    /// The plot area is shrunk to the largest centered area with equal scales, given the limits of the axes,
+    /// so manual limits are kept, and the axes, tick labels, and axis labels move in with the plot area.
+    /// Logarithmic axes are scaled equally per decade. Defaults to `false`, filling the subplot.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let mut subplot = Subplot::builder()
+    ///     .equal_aspect(true)
+    ///     .limits(Axes::X, Limits::Manual { min: -2.0, max: 2.0 })
+    ///     .limits(Axes::Y, Limits::Manual { min: -1.0, max: 1.0 })
+    ///     .build();
+    ///
+    /// // a unit circle, drawn as a circle, in a plot area twice as wide as it is tall
+    /// let angles = (0..=100).map(|i| i as f64 * std::f64::consts::TAU / 100.0);
+    /// let xs = angles.clone().map(f64::cos).collect::<Vec<_>>();
+    /// let ys = angles.map(f64::sin).collect::<Vec<_>>();
+    /// subplot.plot(xs, ys).unwrap();
+    /// ```
+    pub fn equal_aspect(mut self, equal_aspect: bool) -> Self {
+        self.desc.equal_aspect = equal_aspect;
+
+        self
+    }
+
     /// Turns on the major tick mark grid for the primary axes.
     /// Shortcut for calling `.grid(Axes::BothPrimary, Grid::Major)`.
     pub fn standard_grid(self) -> Self {
@@ -410,6 +1348,23 @@ impl<'a> SubplotBuilder<'a> {
         self
     }
 
+    /// Marks axes as showing times, with data as UNIX timestamps in seconds.
+    ///
+    /// Major ticks are placed on whole calendar intervals, such as hours, days, weeks, or months,
+    /// and labeled in UTC with a [`TimeFormat`].
+    #[cfg(feature = "time")]
+    pub fn time_axis(mut self, axes: Axes, format: TimeFormat) -> Self {
+        let axes = self.axes(axes);
+        for axis in axes {
+            axis.major_tick_marks = TickSpacing::Locator(sync::Arc::new(time::locate_ticks));
+            axis.major_tick_labels = TickLabels::Formatter(
+                sync::Arc::new(move |timestamp| time::format(timestamp, format))
+            );
+        }
+
+        self
+    }
+
     /// Sets the visibility of axis lines.
     pub fn visible(mut self, axes: Axes, visible: bool) -> Self {
         let axes = self.axes(axes);
@@ -419,6 +1374,122 @@ impl<'a> SubplotBuilder<'a> {
 
         self
     }
+
+    /// Sets how values are mapped along axes.
+    /// On logarithmic axes, all data and limits must be positive.
+    pub fn scale(mut self, axes: Axes, scale: AxisScale) -> Self {
+        let axes = self.axes(axes);
+        for axis in axes {
+            axis.scale = scale;
+        }
+
+        self
+    }
+
+    /// Sets the fraction of the data range added to each end of automatic limits.
+    /// Defaults to 0.05, and negative margins are treated as 0.0.
+    pub fn autoscale_margin(mut self, axes: Axes, margin: f64) -> Self {
+        let axes = self.axes(axes);
+        for axis in axes {
+            axis.margin = margin.max(0.0);
+        }
+
+        self
+    }
+
+    /// Sets how far automatic limits extend past each end of data without any range,
+    /// such as a single point or a constant, where [`SubplotBuilder::autoscale_margin`] would add nothing.
+    /// Distances are in data units, or in powers of the base on logarithmic axes.
+    /// Defaults to 1.0, and margins that are not positive are ignored.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// // limits from 4.5 to 5.5 around a constant of 5.0, with data flush to the x-axis limits
+    /// let mut subplot = Subplot::builder()
+    ///     .autoscale_margin(Axes::X, 0.0)
+    ///     .autoscale_flat_margin(Axes::Y, 0.5)
+    ///     .build();
+    /// subplot.plot([0.0, 1.0, 2.0], [5.0, 5.0, 5.0]).unwrap();
+    /// ```
+    pub fn autoscale_flat_margin(mut self, axes: Axes, margin: f64) -> Self {
+        if !(margin.is_finite() && margin > 0.0) {
+            return self;
+        }
+
+        let axes = self.axes(axes);
+        for axis in axes {
+            axis.flat_margin = margin;
+        }
+
+        self
+    }
+
+    /// Sets whether the tick label multiplier and offset, such as "x10³",
+    /// are appended to the axis label instead of drawn next to the axis.
+    pub fn modifier_in_label(mut self, axes: Axes, in_label: bool) -> Self {
+        let axes = self.axes(axes);
+        for axis in axes {
+            axis.modifier_in_label = in_label;
+        }
+
+        self
+    }
+
+    /// Rotates tick labels counterclockwise by an angle in degrees, such as 45.0 for dense x-axis labels.
+    /// Defaults to 0.0, and angles that are not finite are ignored.
+    ///
+    /// Space for the rotated labels is measured, and rotated x-axis labels are aligned by their corner
+    /// nearest the axis, so they end, or start, under their ticks.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let mut subplot = Subplot::builder()
+    ///     .tick_label_rotation(Axes::X, 45.0)
+    ///     .major_tick_labels(Axes::X, TickLabels::Manual(vec![
+    ///         "January".to_owned(), "February".to_owned(), "March".to_owned(),
+    ///     ]))
+    ///     .major_tick_marks(Axes::X, TickSpacing::Manual(vec![1.0, 2.0, 3.0]))
+    ///     .build();
+    /// subplot.plot([1.0, 2.0, 3.0], [4.0, 2.0, 5.0]).unwrap();
+    /// ```
+    pub fn tick_label_rotation(mut self, axes: Axes, degrees: f64) -> Self {
+        if !degrees.is_finite() {
+            return self;
+        }
+
+        let axes = self.axes(axes);
+        for axis in axes {
+            axis.tick_label_rotation = degrees;
+        }
+
+        self
+    }
+
+    /// Inverts axes, so values decrease away from the origin, such as for depth increasing downward.
+    /// Each axis inverts independently, including secondary axes.
+    ///
+    /// Inverting reverses whatever limits an axis has, so inverting an axis with manual limits
+    /// with `min` greater than `max` makes it read in increasing order again.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let mut subplot = Subplot::builder()
+    ///     .invert(Axes::Y)
+    ///     .label(Axes::Y, "depth")
+    ///     .build();
+    /// subplot.plot([1.0, 2.0, 3.0], [0.0, 10.0, 25.0]).unwrap();
+    /// ```
+    pub fn invert(mut self, axes: Axes) -> Self {
+        let axes = self.axes(axes);
+        for axis in axes {
+            axis.inverted = true;
+        }
+
+        self
+    }
 }
 impl<'a> SubplotBuilder<'a> {
     fn axes<'b>(&'b mut self, axes: Axes) -> Vec<&'b mut AxisDescriptor<&'a str>> {
@@ -466,6 +1537,21 @@ pub enum Axes {
     BothSecondary,
     All,
 }
+impl Axes {
+    /// Whether an axis draws grid lines, when set as the grid axes and both axes on its side have them.
+    pub(crate) fn owns_grid(self, placement: AxisType) -> bool {
+        match placement {
+            AxisType::X => !matches!(self, Self::SecondaryX | Self::BothSecondary),
+            AxisType::Y => !matches!(self, Self::SecondaryY | Self::BothSecondary),
+            AxisType::SecondaryX => {
+                matches!(self, Self::SecondaryX | Self::BothX | Self::BothSecondary | Self::All)
+            },
+            AxisType::SecondaryY => {
+                matches!(self, Self::SecondaryY | Self::BothY | Self::BothSecondary | Self::All)
+            },
+        }
+    }
+}
 
 /// The formatting for a subplot.
 #[derive(Clone, Debug)]
@@ -475,6 +1561,51 @@ pub struct SubplotFormat {
     /// The color used for filling regions, when there the color cycle is empty.
     pub default_fill_color: Color,
     /// The background color of the plotting area.
+    ///
+    /// The background fills exactly to the inside edges of the axis lines, at any figure size.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let plot_color = Color { r: 0.0, g: 0.0, b: 1.0, a: 1.0 };
+    /// let subplot = Subplot::builder()
+    ///     .format(SubplotFormat {
+    ///         plot_color,
+    ///         tick_direction: TickDirection::None,
+    ///         ..Default::default()
+    ///     })
+    ///     .build();
+    ///
+    /// // an odd number of pixels in each dimension
+    /// let size = FigSize { width: 3.33, height: 2.57 };
+    /// let mut fig = <Figure>::new(&FigureFormat { size, ..Default::default() });
+    /// fig.set_layout(SingleLayout::new(subplot)).unwrap();
+    /// let bitmap = fig.draw_bitmap().unwrap();
+    ///
+    /// let (width, height) = (bitmap.size.width as usize, bitmap.size.height as usize);
+    /// assert_eq!((width % 2, height % 2), (1, 1));
+    /// let pixel = |(x, y): (usize, usize)| {
+    ///     let i = 4 * (y * width + x);
+    ///     [bitmap.data[i], bitmap.data[i + 1], bitmap.data[i + 2], bitmap.data[i + 3]]
+    /// };
+    /// let (spine, face, background) = ([0, 0, 0, 255], [255, 255, 255, 255], [0, 0, 255, 255]);
+    ///
+    /// // walk in from each side through the middle of the figure, across the axis line
+    /// let (xmid, ymid) = (width / 2, height / 2);
+    /// let paths: [Vec<(usize, usize)>; 4] = [
+    ///     (0..width).map(|x| (x, ymid)).collect(),
+    ///     (0..width).rev().map(|x| (x, ymid)).collect(),
+    ///     (0..height).map(|y| (xmid, y)).collect(),
+    ///     (0..height).rev().map(|y| (xmid, y)).collect(),
+    /// ];
+    /// for path in paths {
+    ///     let start = path.iter().position(|&p| pixel(p) == spine).unwrap();
+    ///     let end = start + path[start..].iter().position(|&p| pixel(p) != spine).unwrap();
+    ///
+    ///     assert_eq!(pixel(path[start - 1]), face);
+    ///     assert_eq!(pixel(path[end]), background);
+    /// }
+    /// ```
     pub plot_color: Color,
     /// The default width of all nonplot lines in the subplot.
     pub line_width: u32,
@@ -482,10 +1613,57 @@ pub struct SubplotFormat {
     pub line_color: Color,
     /// The color of grid lines.
     pub grid_color: Color,
+    /// Overrides the color of minor grid lines.
+    /// Otherwise [`Self::grid_color`] is used.
+    pub minor_grid_color: Option<Color>,
+    /// Overrides the width of minor grid lines.
+    /// Otherwise [`Self::line_width`] is used, the same as major grid lines.
+    pub minor_grid_width: Option<u32>,
+    /// The style of minor grid lines, to tell them apart from major grid lines.
+    /// Defaults to [`LineStyle::Dashed`], and `None` draws them solid.
+    pub minor_grid_style: Option<LineStyle>,
     /// The name of the default font used.
+    /// Any installed font family can be used by name with [`FontName::Custom`].
     pub font_name: FontName,
     /// The size of the default font used.
     pub font_size: f32,
+    /// Overrides the font size of tick labels and their multiplier and offset.
+    /// Otherwise [`Self::font_size`] is used.
+    pub tick_label_font_size: Option<f32>,
+    /// Overrides the font size of axis labels.
+    /// Otherwise [`Self::font_size`] is used.
+    pub axis_label_font_size: Option<f32>,
+    /// Overrides the font size of the title.
+    /// Otherwise [`Self::font_size`] is used.
+    pub title_font_size: Option<f32>,
+    /// Overrides the whole font of axis labels, including its slant and weight.
+    /// Takes precedence over [`Self::font_name`] and [`Self::axis_label_font_size`].
+    pub label_font: Option<Font>,
+    /// Overrides the whole font of the title, including its slant and weight.
+    /// Takes precedence over [`Self::font_name`] and [`Self::title_font_size`].
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let format = SubplotFormat {
+    ///     title_font: Some(Font {
+    ///         size: 28.0,
+    ///         weight: FontWeight::Bold,
+    ///         ..Default::default()
+    ///     }),
+    ///     ..Default::default()
+    /// };
+    /// let mut subplot = Subplot::builder()
+    ///     .format(format)
+    ///     .title("bold title")
+    ///     .build();
+    /// subplot.plot([0.0, 1.0], [0.0, 1.0]).unwrap();
+    ///
+    /// let mut fig = <Figure>::default();
+    /// fig.set_layout(SingleLayout::new(subplot)).unwrap();
+    /// fig.draw_bitmap().unwrap();
+    /// ```
+    pub title_font: Option<Font>,
     /// The default color of text.
     pub text_color: Color,
     /// The length of major tick marks, from center of the axis, out.
@@ -497,8 +1675,20 @@ pub struct SubplotFormat {
     pub override_minor_tick_length: Option<u32>,
     /// The default colors cycled through for plot marker and line colors.
     pub color_cycle: Vec<Color>,
+    /// The alpha value of fill colors taken from [`Self::color_cycle`], from 0.0 to 1.0.
+    pub fill_alpha: f64,
+    /// The width of the colorbar, if one is drawn.
+    pub colorbar_width: u32,
 }
 impl SubplotFormat {
+    /// Gets color `i` of [`Self::color_cycle`], wrapping around past the end of the cycle.
+    ///
+    /// Falls back to [`Self::default_marker_color`] when the color cycle is empty.
+    pub fn cycle_color(&self, i: usize) -> Color {
+        i.checked_rem(self.color_cycle.len())
+            .map_or(self.default_marker_color, |i| self.color_cycle[i])
+    }
+
     /// Constructor for a dark themed format.
     pub fn dark() -> Self {
         let line_color = Color { r: 0.659, g: 0.600, b: 0.518, a: 1.0 };
@@ -515,15 +1705,25 @@ impl SubplotFormat {
             default_fill_color: Color { r: 1.0, g: 0.0, b: 0.0, a: 0.5 },
             plot_color: Color { r: 0.157, g: 0.157, b: 0.157, a: 1.0 },
             grid_color: Color { r: 0.250, g: 0.250, b: 0.250, a: 1.0 },
+            minor_grid_color: None,
+            minor_grid_width: None,
+            minor_grid_style: Some(LineStyle::Dashed),
             line_width: 2,
             line_color,
             font_name: FontName::default(),
             font_size: 20.0,
+            tick_label_font_size: None,
+            axis_label_font_size: None,
+            title_font_size: None,
+            label_font: None,
+            title_font: None,
             text_color: line_color,
             tick_length: 8,
             tick_direction: TickDirection::Inner,
             override_minor_tick_length: None,
             color_cycle,
+            fill_alpha: 0.5,
+            colorbar_width: 20,
         }
     }
 }
@@ -544,13 +1744,23 @@ impl Default for SubplotFormat {
             line_width: 2,
             line_color: Color::BLACK,
             grid_color: Color { r: 0.750, g: 0.750, b: 0.750, a: 1.0 },
+            minor_grid_color: None,
+            minor_grid_width: None,
+            minor_grid_style: Some(LineStyle::Dashed),
             font_name: FontName::default(),
             font_size: 20.0,
+            tick_label_font_size: None,
+            axis_label_font_size: None,
+            title_font_size: None,
+            label_font: None,
+            title_font: None,
             text_color: Color::BLACK,
             tick_length: 8,
             tick_direction: TickDirection::Inner,
             override_minor_tick_length: None,
             color_cycle,
+            fill_alpha: 0.5,
+            colorbar_width: 20,
         }
     }
 }
@@ -564,10 +1774,21 @@ pub enum TickDirection {
     Outer,
     /// Ticks are both inside and outside the axis lines.
     Both,
+    /// No tick marks are drawn, though tick labels are, in the same place as for inner ticks.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let mut subplot = Subplot::builder()
+    ///     .format(SubplotFormat { tick_direction: TickDirection::None, ..Default::default() })
+    ///     .build();
+    /// subplot.plot([0.0, 1.0], [0.0, 1.0]).unwrap();
+    /// ```
+    None,
 }
 
 /// Describes how tick mark locations are determined, if at all.
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub enum TickSpacing {
     /// Tick marks are present and located by the library.
     On,
@@ -579,10 +1800,45 @@ pub enum TickSpacing {
     Count(u16),
     /// Tick marks are manually placed.
     Manual(Vec<f64>),
+    /// Tick marks are placed by a function of the axis limits, from lower to upper.
+    /// Returned locations outside of the limits are ignored.
+    Locator(sync::Arc<dyn Fn(f64, f64) -> Vec<f64>>),
+    /// Tick marks are placed at each integer multiple of a positive base within the limits,
+    /// such as multiples of π for trigonometric functions.
+    ///
+    /// Drawing fails if the base gives more than 1000 ticks within the limits.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let mut subplot = Subplot::builder()
+    ///     .limits(Axes::X, Limits::Manual { min: -1e300, max: 1e300 })
+    ///     .major_tick_marks(Axes::X, TickSpacing::MultiplesOf(1.0))
+    ///     .build();
+    /// subplot.plot([0.0, 1.0], [0.0, 1.0]).unwrap();
+    ///
+    /// let mut fig = <Figure>::default();
+    /// fig.set_layout(SingleLayout::new(subplot)).unwrap();
+    /// assert!(fig.draw_bitmap().is_err());
+    /// ```
+    MultiplesOf(f64),
+}
+impl fmt::Debug for TickSpacing {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::On => write!(f, "On"),
+            Self::Auto => write!(f, "Auto"),
+            Self::None => write!(f, "None"),
+            Self::Count(n) => f.debug_tuple("Count").field(n).finish(),
+            Self::Manual(ticks) => f.debug_tuple("Manual").field(ticks).finish(),
+            Self::Locator(_) => write!(f, "Locator"),
+            Self::MultiplesOf(base) => f.debug_tuple("MultiplesOf").field(base).finish(),
+        }
+    }
 }
 
 /// Describes how and whether tick mark labels are set.
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub enum TickLabels {
     /// Tick labels are present and determined by the library.
     On,
@@ -592,6 +1848,85 @@ pub enum TickLabels {
     None,
     /// Tick labels are manually set.
     Manual(Vec<String>),
+    /// Tick labels are formatted from tick locations by a function.
+    /// Locations are in data units, without the offset or multiplier of automatic labels.
+    Formatter(sync::Arc<dyn Fn(f64) -> String>),
+    /// Tick labels are written as multiples of a base, named by a symbol.
+    /// Multiples are written as fractions with denominators up to 12 where possible,
+    /// such as "π/2", "π", and "3π/2" for a base of π with the symbol "π".
+    MultiplesOf(f64, String),
+}
+impl fmt::Debug for TickLabels {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::On => write!(f, "On"),
+            Self::Auto => write!(f, "Auto"),
+            Self::None => write!(f, "None"),
+            Self::Manual(labels) => f.debug_tuple("Manual").field(labels).finish(),
+            Self::Formatter(_) => write!(f, "Formatter"),
+            Self::MultiplesOf(base, symbol) => {
+                f.debug_tuple("MultiplesOf").field(base).field(symbol).finish()
+            },
+        }
+    }
+}
+
+/// How times are labeled on a time axis, set with [`SubplotBuilder::time_axis`].
+#[cfg(feature = "time")]
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TimeFormat {
+    /// The year, such as "2024".
+    Year,
+    /// The year and month, such as "2024-03".
+    YearMonth,
+    /// The year, month, and day, such as "2024-03-05".
+    Date,
+    /// The abbreviated month and day, such as "Mar 5".
+    MonthDay,
+    /// The hour and minute, such as "14:30".
+    HourMinute,
+    /// The hour, minute, and second, such as "14:30:05", with any fraction of a second.
+    HourMinuteSecond,
+}
+
+/// How values are mapped along an axis.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum AxisScale {
+    /// Values are evenly spaced.
+    #[default]
+    Linear,
+    /// Powers of 10 are evenly spaced.
+    /// Automatic major ticks are placed on each power of 10, with minor ticks on 2 to 9 times each power.
+    Log10,
+    /// Powers of e are evenly spaced.
+    /// Automatic major ticks are placed on each power of e.
+    Ln,
+}
+impl AxisScale {
+    /// Maps a value to where it is evenly spaced.
+    pub(crate) fn transform(self, value: f64) -> f64 {
+        match self {
+            Self::Linear => value,
+            Self::Log10 => value.log10(),
+            Self::Ln => value.ln(),
+        }
+    }
+    /// The fraction of the way from the first to second limit that a value is placed.
+    pub(crate) fn fraction(self, value: f64, limits: (f64, f64)) -> f64 {
+        let (first, second) = (self.transform(limits.0), self.transform(limits.1));
+
+        (self.transform(value) - first) / (second - first)
+    }
+    /// Maps an evenly spaced value back.
+    pub(crate) fn inverse(self, value: f64) -> f64 {
+        match self {
+            Self::Linear => value,
+            Self::Log10 => f64::powf(10.0, value),
+            Self::Ln => value.exp(),
+        }
+    }
 }
 
 /// Indicates which, if any, tick marks on an axis should have grid lines.
@@ -610,7 +1945,37 @@ pub enum Grid {
 pub enum Limits {
     /// Limits are determined by the library.
     Auto,
+    /// Limits are determined by the library, expanded to always include a value, such as a baseline of 0.0.
+    AutoInclude(f64),
     /// Limits are set manually.
+    /// The axis is reversed when `min` is greater than `max`.
+    ///
+    /// ```
+    /// use plt::*;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// // record the ticks as they are labeled
+    /// let ticks = Arc::new(Mutex::new(Vec::new()));
+    /// let labeled = ticks.clone();
+    /// let formatter = move |tick: f64| {
+    ///     labeled.lock().unwrap().push(tick);
+    ///     tick.to_string()
+    /// };
+    ///
+    /// let mut subplot = Subplot::builder()
+    ///     .limits(Axes::X, Limits::Manual { min: 10.0, max: 0.0 })
+    ///     .major_tick_labels(Axes::X, TickLabels::Formatter(Arc::new(formatter)))
+    ///     .build();
+    /// subplot.plot([0.0, 10.0], [0.0, 1.0]).unwrap();
+    /// assert_eq!(subplot.limits(Axes::X), Some((10.0, 0.0)));
+    ///
+    /// let mut fig = <Figure>::default();
+    /// fig.set_layout(SingleLayout::new(subplot)).unwrap();
+    /// fig.draw_bitmap().unwrap();
+    ///
+    /// // ticks run from the left of the plot area, at the maximum
+    /// assert_eq!(*ticks.lock().unwrap(), [10.0, 7.5, 5.0, 2.5, 0.0]);
+    /// ```
     Manual { min: f64, max: f64 },
 }
 
@@ -643,8 +2008,32 @@ impl<'a, 'b> Plotter<'a, 'b> {
             ));
         } else if xdata.clone().any(|x| x.is_nan()) {
             return Err(PltError::InvalidData("x-data has NaN value".to_owned()));
-        } else if ydata.clone().any(|y| y.is_nan()) {
+        } else if !self.desc.allow_gaps && ydata.clone().any(|y| y.is_nan()) {
             return Err(PltError::InvalidData("y-data has NaN value".to_owned()));
+        } else if ydata.len() > 0 && ydata.clone().all(|y| y.is_nan()) {
+            return Err(PltError::InvalidData("y-data has only NaN values".to_owned()));
+        }
+
+        for (name, errors) in [("x", &self.desc.xerr), ("y", &self.desc.yerr)] {
+            if let Some(errors) = errors {
+                if errors.len() != xdata.len() {
+                    return Err(PltError::InvalidData(format!(
+                        "Data is not correctly sized. There should be one {}-error for each point", name,
+                    )));
+                } else if errors.iter().any(|err| err.is_nan() || *err < 0.0) {
+                    return Err(PltError::InvalidData(format!(
+                        "{}-errors have a negative or NaN value", name,
+                    )));
+                }
+            }
+        }
+
+        if let Some(colors) = &self.desc.marker_colors {
+            if colors.len() != xdata.len() {
+                return Err(PltError::InvalidData(
+                    "Data is not correctly sized. There should be one color value for each point".to_owned()
+                ));
+            }
         }
 
         let data = PlotData::new(xdata, ydata);
@@ -654,6 +2043,74 @@ impl<'a, 'b> Plotter<'a, 'b> {
         Ok(())
     }
 
+    /// Borrows data to be plotted and consumes the plotter,
+    /// returning a handle to the series for changing it later.
+    pub fn plot_handle<Xs, Ys, Fx, Fy>(
+        self,
+        xs: Xs,
+        ys: Ys,
+    ) -> Result<SeriesHandle, PltError>
+    where
+        Fx: IntoF64,
+        Fy: IntoF64,
+        Xs: IntoIterator<Item=Fx>,
+        Ys: IntoIterator<Item=Fy>,
+        <Xs as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+        <Ys as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+    {
+        let index = self.subplot.plot_order.len();
+
+        self.plot(xs, ys)?;
+
+        Ok(SeriesHandle(index))
+    }
+
+    /// Borrows data to be plotted as markers colored by values, and consumes the plotter.
+    ///
+    /// Values are normalized over their range, or the color limits if set,
+    /// and mapped to colors with the colormap, which defaults to [`Colormap::Viridis`].
+    /// Lines are not drawn, and the legend shows the middle color of the colormap.
+    pub fn scatter_c<Xs, Ys, Vs, Fx, Fy, Fv>(
+        mut self,
+        xs: Xs,
+        ys: Ys,
+        values: Vs,
+    ) -> Result<(), PltError>
+    where
+        Fx: IntoF64,
+        Fy: IntoF64,
+        Fv: IntoF64,
+        Xs: IntoIterator<Item=Fx>,
+        Ys: IntoIterator<Item=Fy>,
+        Vs: IntoIterator<Item=Fv>,
+        <Xs as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+        <Ys as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+    {
+        let values = values.into_iter().map(|f| f.f64()).collect::<Vec<_>>();
+        if values.iter().any(|value| !value.is_finite()) {
+            return Err(PltError::InvalidData("color values have NaN or infinite value".to_owned()));
+        } else if let Some((min, max)) = self.desc.color_limits
+            .filter(|(min, max)| !(min.is_finite() && max.is_finite()))
+        {
+            return Err(PltError::InvalidData(format!("color limits of {} to {} are not finite", min, max)));
+        }
+
+        let (min, max) = self.desc.color_limits.unwrap_or((
+            values.iter().copied().fold(f64::INFINITY, f64::min),
+            values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        ));
+        let (colormap, normalization) = (self.desc.colormap, self.desc.normalization);
+
+        self.desc.marker_colors = Some(values.iter()
+            .map(|&value| colormap.sample(normalization.normalize(value, min, max)))
+            .collect());
+        self.desc.line = false;
+        self.desc.marker = true;
+        self.desc.marker_format.color_override.get_or_insert(colormap.sample(0.5));
+
+        self.plot(xs, ys)
+    }
+
     /// Borrows step data to be plotted and consumes the plotter.
     pub fn step<Xs, Ys, Fx, Fy>(
         mut self,
@@ -671,21 +2128,161 @@ impl<'a, 'b> Plotter<'a, 'b> {
         let step_data = steps.into_iter().map(|f| f.f64());
         let ydata = ys.into_iter().map(|f| f.f64());
 
-        if step_data.len() != ydata.len() + 1 {
-            return Err(PltError::InvalidData(
-                "Data is not correctly sized. There should be one more step than y-value".to_owned()
-            ));
-        } else if step_data.clone().any(|step| step.is_nan()) {
-            return Err(PltError::InvalidData("step-data has NaN value".to_owned()));
+        if step_data.len() != ydata.len() + 1 {
+            return Err(PltError::InvalidData(
+                "Data is not correctly sized. There should be one more step than y-value".to_owned()
+            ));
+        } else if step_data.clone().any(|step| step.is_nan()) {
+            return Err(PltError::InvalidData("step-data has NaN value".to_owned()));
+        } else if ydata.clone().any(|y| y.is_nan()) {
+            return Err(PltError::InvalidData("y-data has NaN value".to_owned()));
+        } else if self.desc.xerr.is_some() || self.desc.yerr.is_some() {
+            return Err(PltError::InvalidData("step data cannot have error bars".to_owned()));
+        } else if self.desc.marker_colors.is_some() {
+            return Err(PltError::InvalidData("step data cannot have color mapped markers".to_owned()));
+        }
+
+        self.desc.pixel_perfect = true;
+
+        let data = StepData::new(step_data, ydata);
+
+        self.subplot.plot_desc(self.desc, data);
+
+        Ok(())
+    }
+
+    /// Borrows data to be drawn as vertical bars, one centered on each x-value, and consumes the plotter.
+    /// Bars extend from the base by their heights, so negative heights draw downward.
+    pub fn bar<Xs, Ys, Fx, Fy>(
+        self,
+        xs: Xs,
+        heights: Ys,
+    ) -> Result<(), PltError>
+    where
+        Fx: IntoF64,
+        Fy: IntoF64,
+        Xs: IntoIterator<Item=Fx>,
+        Ys: IntoIterator<Item=Fy>,
+        <Xs as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+        <Ys as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+    {
+        let xdata = xs.into_iter().map(|f| f.f64());
+        let hdata = heights.into_iter().map(|f| f.f64());
+
+        if xdata.len() != hdata.len() {
+            return Err(PltError::InvalidData(
+                "Data is not correctly sized. There should be one height for each x-value".to_owned()
+            ));
+        } else if xdata.len() == 0 {
+            return Err(PltError::InvalidData("bar data is empty".to_owned()));
+        } else if xdata.clone().any(|x| x.is_nan()) {
+            return Err(PltError::InvalidData("x-data has NaN value".to_owned()));
+        } else if hdata.clone().any(|h| h.is_nan()) {
+            return Err(PltError::InvalidData("height data has NaN value".to_owned()));
+        } else if !(self.desc.bar_width > 0.0 && self.desc.bar_width.is_finite()) {
+            return Err(PltError::InvalidData(format!(
+                "bar width of {} is not a positive number", self.desc.bar_width,
+            )));
+        } else if !self.desc.bar_base.is_finite() {
+            return Err(PltError::InvalidData(format!(
+                "bar base of {} is not finite", self.desc.bar_base,
+            )));
+        }
+
+        let data = PlotData::new(xdata, hdata);
+
+        self.subplot.bar_desc(self.desc, data);
+
+        Ok(())
+    }
+
+    /// Borrows data to be drawn as stems, and consumes the plotter.
+    ///
+    /// Each point is drawn as a vertical line from the baseline to the point, with a marker at the point,
+    /// using the line and marker formats, so stems are drawn even if lines or markers are turned off.
+    /// The line and marker take the same color from [`SubplotFormat::color_cycle`], unless overridden.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let mut subplot = Subplot::builder().build();
+    ///
+    /// // automatic limits include the baseline, so the y-axis starts at about 1.0
+    /// subplot.plotter()
+    ///     .stem_baseline(1.0)
+    ///     .marker(Some(MarkerStyle::Square))
+    ///     .stem([0.0, 1.0, 2.0], [2.0, 3.0, 4.0])
+    ///     .unwrap();
+    ///
+    /// assert!(subplot.plotter().stem([0.0, 1.0], [f64::NAN, 1.0]).is_err());
+    /// ```
+    pub fn stem<Xs, Ys, Fx, Fy>(
+        self,
+        xs: Xs,
+        ys: Ys,
+    ) -> Result<(), PltError>
+    where
+        Fx: IntoF64,
+        Fy: IntoF64,
+        Xs: IntoIterator<Item=Fx>,
+        Ys: IntoIterator<Item=Fy>,
+        <Xs as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+        <Ys as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+    {
+        let xdata = xs.into_iter().map(|f| f.f64());
+        let ydata = ys.into_iter().map(|f| f.f64());
+
+        if xdata.len() != ydata.len() {
+            return Err(PltError::InvalidData("Data is not correctly sized".to_owned()));
+        } else if xdata.len() == 0 {
+            return Err(PltError::InvalidData("stem data is empty".to_owned()));
+        } else if xdata.clone().any(|x| x.is_nan()) {
+            return Err(PltError::InvalidData("x-data has NaN value".to_owned()));
         } else if ydata.clone().any(|y| y.is_nan()) {
             return Err(PltError::InvalidData("y-data has NaN value".to_owned()));
+        } else if !self.desc.stem_baseline.is_finite() {
+            return Err(PltError::InvalidData(format!(
+                "stem baseline of {} is not finite", self.desc.stem_baseline,
+            )));
+        } else if self.desc.xerr.is_some() || self.desc.yerr.is_some() {
+            return Err(PltError::InvalidData("stem data cannot have error bars".to_owned()));
+        } else if self.desc.marker_colors.is_some() {
+            return Err(PltError::InvalidData("stem data cannot have color mapped markers".to_owned()));
         }
 
-        self.desc.pixel_perfect = true;
+        let data = PlotData::new(xdata, ydata);
 
-        let data = StepData::new(step_data, ydata);
+        self.subplot.stem_desc(self.desc, data);
 
-        self.subplot.plot_desc(self.desc, data);
+        Ok(())
+    }
+
+    /// Draws a grid of values as cells colored by a colormap, and consumes the plotter.
+    ///
+    /// The cell in row `i` and column `j` covers x-values from `j` to `j + 1` and y-values from `i` to `i + 1`,
+    /// so the first row is at the bottom, and automatic limits fit the grid without margins.
+    /// Values are normalized over their range, or the color limits if set,
+    /// and mapped to colors with the colormap, which defaults to [`Colormap::Viridis`].
+    pub fn heatmap(self, data: ndarray::Array2<f64>) -> Result<(), PltError> {
+        if data.is_empty() {
+            return Err(PltError::InvalidData("heatmap data is empty".to_owned()));
+        } else if data.iter().any(|value| !value.is_finite()) {
+            return Err(PltError::InvalidData("heatmap data has NaN or infinite value".to_owned()));
+        } else if let Some((min, max)) = self.desc.color_limits
+            .filter(|(min, max)| !(min.is_finite() && max.is_finite()))
+        {
+            return Err(PltError::InvalidData(format!("color limits of {} to {} are not finite", min, max)));
+        }
+
+        let (min, max) = self.desc.color_limits.unwrap_or((
+            data.iter().copied().fold(f64::INFINITY, f64::min),
+            data.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        ));
+        let (colormap, normalization) = (self.desc.colormap, self.desc.normalization);
+
+        let colors = data.mapv(|value| colormap.sample(normalization.normalize(value, min, max)));
+
+        self.subplot.heatmap_desc(self.desc, colors);
 
         Ok(())
     }
@@ -775,6 +2372,30 @@ impl<'a, 'b> Plotter<'a, 'b> {
         self
     }
 
+    /// Sets whether markers are filled. Defaults to `true`.
+    ///
+    /// Unfilled markers are hollow, always drawn with an outline,
+    /// which takes the marker color unless overridden with [`Plotter::marker_outline_color`].
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let mut subplot = Subplot::builder().build();
+    ///
+    /// // hollow circles, outlined in the next color of the color cycle
+    /// subplot.plotter()
+    ///     .line(None)
+    ///     .marker(Some(MarkerStyle::Circle))
+    ///     .marker_fill(false)
+    ///     .plot([0.0, 1.0, 2.0], [0.0, 4.0, 1.0])
+    ///     .unwrap();
+    /// ```
+    pub fn marker_fill(mut self, fill: bool) -> Self {
+        self.desc.marker_format.fill = fill;
+
+        self
+    }
+
     /// Overrides the default outline color for marker outlines.
     /// By default, marker outline colors are determined by cycling through [`SubplotFormat::color_cycle`].
     pub fn marker_outline_color(mut self, color: Color) -> Self {
@@ -797,6 +2418,167 @@ impl<'a, 'b> Plotter<'a, 'b> {
 
         self
     }
+
+    /// Randomly offsets each point horizontally by up to `amount`, in x-data units.
+    /// Useful for spreading out overlapping points in categorical scatter plots.
+    ///
+    /// The amount is limited to half the smallest spacing between distinct x-values,
+    /// so points never cross into a neighboring category.
+    #[cfg(feature = "jitter")]
+    pub fn jitter(mut self, amount: f64) -> Self {
+        let seed = self.desc.jitter.and_then(|jitter| jitter.seed);
+        self.desc.jitter = Some(Jitter { amount: amount.max(0.0), seed });
+
+        self
+    }
+
+    /// Sets the seed used to generate jitter offsets, so the same seed gives the same offsets.
+    /// By default, the seed is derived from the figure's seed, set by [`Figure::set_seed`](crate::Figure::set_seed).
+    #[cfg(feature = "jitter")]
+    pub fn jitter_seed(mut self, seed: u64) -> Self {
+        let amount = self.desc.jitter.map_or(0.0, |jitter| jitter.amount);
+        self.desc.jitter = Some(Jitter { amount, seed: Some(seed) });
+
+        self
+    }
+
+    /// Draws vertical error bars, from `y - error` to `y + error` at each point.
+    /// There must be one error for each point, and errors cannot be negative.
+    pub fn yerr<Es, Fe>(mut self, errors: Es) -> Self
+    where
+        Fe: IntoF64,
+        Es: IntoIterator<Item=Fe>,
+    {
+        self.desc.yerr = Some(errors.into_iter().map(|f| f.f64()).collect());
+
+        self
+    }
+
+    /// Draws horizontal error bars, from `x - error` to `x + error` at each point.
+    /// There must be one error for each point, and errors cannot be negative.
+    pub fn xerr<Es, Fe>(mut self, errors: Es) -> Self
+    where
+        Fe: IntoF64,
+        Es: IntoIterator<Item=Fe>,
+    {
+        self.desc.xerr = Some(errors.into_iter().map(|f| f.f64()).collect());
+
+        self
+    }
+
+    /// Sets the length of the caps across the ends of error bars.
+    /// Caps are not drawn with a length of 0.
+    pub fn errorbar_cap_size(mut self, size: u32) -> Self {
+        self.desc.errorbar_cap_size = size;
+
+        self
+    }
+
+    /// Overrides the default error bar color.
+    /// By default, error bars are the same color as the line, or the markers if there is no line.
+    pub fn errorbar_color(mut self, color: Color) -> Self {
+        self.desc.errorbar_color = Some(color);
+
+        self
+    }
+
+    /// Sets the width of bars drawn by [`Plotter::bar`], in x-data units.
+    /// Defaults to 0.8.
+    pub fn bar_width(mut self, width: f64) -> Self {
+        self.desc.bar_width = width;
+
+        self
+    }
+
+    /// Sets the y-value that bars drawn by [`Plotter::bar`] extend from.
+    /// Defaults to 0.0.
+    pub fn bar_base(mut self, base: f64) -> Self {
+        self.desc.bar_base = base;
+
+        self
+    }
+
+    /// Sets the y-value that stems drawn by [`Plotter::stem`] extend from.
+    /// Defaults to 0.0.
+    pub fn stem_baseline(mut self, baseline: f64) -> Self {
+        self.desc.stem_baseline = baseline;
+
+        self
+    }
+
+    /// Allows NaN y-values in data drawn by [`Plotter::plot`], for missing data.
+    /// Lines are broken at NaN values, instead of connecting the points on either side,
+    /// and markers and error bars are not drawn for them.
+    /// Defaults to `false`, returning [`PltError::InvalidData`] for NaN values.
+    ///
+    /// ```
+    /// let mut sp = plt::Subplot::builder().build();
+    ///
+    /// // drawn as two lines, from 0.0 to 1.0, and from 3.0 to 4.0
+    /// sp.plotter()
+    ///     .allow_gaps(true)
+    ///     .plot([0.0, 1.0, 2.0, 3.0, 4.0], [0.0, 1.0, f64::NAN, 3.0, 4.0])
+    ///     .unwrap();
+    ///
+    /// assert!(sp.plot([0.0, 1.0], [f64::NAN, 1.0]).is_err());
+    /// ```
+    pub fn allow_gaps(mut self, allow_gaps: bool) -> Self {
+        self.desc.allow_gaps = allow_gaps;
+
+        self
+    }
+
+    /// Sets the order this data is drawn in, relative to other data on the subplot.
+    /// Data with a higher z-order is drawn on top, and data with equal z-order
+    /// is drawn in the order it was added. Defaults to 0.
+    ///
+    /// ```
+    /// let xs = [0.0, 1.0, 2.0];
+    ///
+    /// let mut sp = plt::Subplot::builder().build();
+    /// // drawn above the line, even though it is added first
+    /// sp.plotter().zorder(1).plot(&xs, &[0.0, 1.0, 0.0]).unwrap();
+    /// sp.plot(&xs, &[1.0, 0.0, 1.0]).unwrap();
+    /// ```
+    pub fn zorder(mut self, zorder: i32) -> Self {
+        self.desc.zorder = zorder;
+
+        self
+    }
+
+    /// Overrides the default color of bars drawn by [`Plotter::bar`].
+    /// By default, bar colors are determined by cycling through [`SubplotFormat::color_cycle`].
+    pub fn bar_color(mut self, color: Color) -> Self {
+        self.desc.bar_color = Some(color);
+
+        self
+    }
+
+    /// Sets the colormap of markers drawn by [`Plotter::scatter_c`] and cells drawn by [`Plotter::heatmap`].
+    /// Defaults to [`Colormap::Viridis`].
+    pub fn colormap(mut self, colormap: Colormap) -> Self {
+        self.desc.colormap = colormap;
+
+        self
+    }
+
+    /// Sets how values of [`Plotter::scatter_c`] and [`Plotter::heatmap`] are mapped to the colormap.
+    /// Defaults to [`Normalization::Linear`].
+    pub fn normalization(mut self, normalization: Normalization) -> Self {
+        self.desc.normalization = normalization;
+
+        self
+    }
+
+    /// Sets the values normalized to the ends of the colormap by [`Plotter::scatter_c`] and [`Plotter::heatmap`],
+    /// so colors are comparable between plots.
+    /// Values outside of the limits take the color at the nearest end.
+    /// By default, the minimum and maximum of the values are used.
+    pub fn color_limits(mut self, min: f64, max: f64) -> Self {
+        self.desc.color_limits = Some((min, max));
+
+        self
+    }
 }
 
 /// Fills a region of a subplot with a color.
@@ -834,25 +2616,387 @@ impl<'a, 'b> Filler<'a, 'b> {
         Ok(())
     }
 
-    /// Uses the secondary Y-Axis to reference y-data.
-    pub fn use_secondary_yaxis(mut self) -> Self {
-        self.desc.yaxis = AxisType::SecondaryY;
+    /// Fills an area between a curve and a series already plotted on the subplot.
+    ///
+    /// Where the x-values of the curve and the series do not line up,
+    /// each is linearly interpolated between its points, so the fill follows both lines as drawn.
+    /// The curve and the series must have increasing x-values, the series must cover the x-values of the curve,
+    /// and the series must be referenced to the same axes as the fill.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let mut subplot = Subplot::builder().build();
+    /// let baseline = subplot.plot_handle([0.0, 2.0, 4.0], [1.0, 1.0, 3.0]).unwrap();
+    ///
+    /// // x-values between the points of the baseline are interpolated
+    /// subplot.filler().fill_to_series([0.0, 1.0, 3.0, 4.0], [2.0, 3.0, 4.0, 5.0], baseline).unwrap();
+    ///
+    /// // x-values outside of the baseline cannot be filled to
+    /// assert!(subplot.filler().fill_to_series([-1.0, 1.0], [2.0, 3.0], baseline).is_err());
+    /// ```
+    pub fn fill_to_series<Xs, Ys, Fx, Fy>(
+        self,
+        xs: Xs,
+        ys: Ys,
+        series: SeriesHandle,
+    ) -> Result<(), PltError>
+    where
+        Fx: IntoF64,
+        Fy: IntoF64,
+        Xs: IntoIterator<Item=Fx>,
+        Ys: IntoIterator<Item=Fy>,
+    {
+        let xdata = xs.into_iter().map(|f| f.f64()).collect::<Vec<_>>();
+        let ydata = ys.into_iter().map(|f| f.f64()).collect::<Vec<_>>();
+
+        if xdata.len() != ydata.len() {
+            return Err(PltError::InvalidData(
+                "Data is not correctly sized. There should be one y-value for each x-value".to_owned()
+            ));
+        }
+
+        let points = iter::zip(xdata, ydata).collect::<Vec<_>>();
+        if points.iter().any(|(x, _)| x.is_nan()) {
+            return Err(PltError::InvalidData("x-data has NaN value".to_owned()));
+        } else if points.iter().any(|(_, y)| y.is_nan()) {
+            return Err(PltError::InvalidData("y-data has NaN value".to_owned()));
+        } else if !increasing(&points) {
+            return Err(PltError::InvalidData("x-data is not increasing".to_owned()));
+        }
+
+        let index = series.index();
+        let info = match self.subplot.series_index(series)? {
+            (PlotType::Series, nth) => &self.subplot.plot_infos[nth],
+            _ => {
+                return Err(PltError::InvalidData(format!(
+                    "series {} is not a plotted series, and cannot be filled to", index,
+                )));
+            },
+        };
+
+        if info.xaxis != self.desc.xaxis || info.yaxis != self.desc.yaxis {
+            return Err(PltError::InvalidData(format!(
+                "series {} is referenced to different axes than the fill", index,
+            )));
+        }
+
+        let series_points = info.data.data().collect::<Vec<_>>();
+        if !increasing(&series_points) {
+            return Err(PltError::InvalidData(format!(
+                "series {} does not have increasing x-values", index,
+            )));
+        }
+
+        // both boundaries bend at the points of either curve, so they follow the drawn lines
+        let (xmin, xmax) = match (points.first(), points.last()) {
+            (Some(&(xmin, _)), Some(&(xmax, _))) => (xmin, xmax),
+            _ => return Err(PltError::InvalidData("Data is empty".to_owned())),
+        };
+        let mut xdata = Iterator::chain(points.iter(), series_points.iter())
+            .map(|&(x, _)| x)
+            .filter(|&x| x >= xmin && x <= xmax)
+            .collect::<Vec<_>>();
+        xdata.sort_by(f64::total_cmp);
+        xdata.dedup();
+
+        let ydata = xdata.iter()
+            .map(|&x| interpolate(&points, x).unwrap())
+            .collect::<Vec<_>>();
+        let series_ydata = xdata.iter()
+            .map(|&x| interpolate(&series_points, x).ok_or_else(|| PltError::InvalidData(format!(
+                "x-value {} is outside of the x-values of series {}", x, index,
+            ))))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let data = FillBetweenData::new(xdata.into_iter(), ydata.into_iter(), series_ydata.into_iter());
+
+        self.subplot.fill_between_desc(self.desc, data);
+
+        Ok(())
+    }
+
+    /// Fills the area between step data and the baseline on the subplot.
+    /// The filled area lines up with the same data drawn by [`Plotter::step`].
+    pub fn fill_step<Xs, Ys, Fx, Fy>(
+        mut self,
+        steps: Xs,
+        ys: Ys,
+    ) -> Result<(), PltError>
+    where
+        Fx: IntoF64,
+        Fy: IntoF64,
+        Xs: IntoIterator<Item=Fx>,
+        Ys: IntoIterator<Item=Fy>,
+        <Xs as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+        <Ys as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+    {
+        let step_data = steps.into_iter().map(|f| f.f64());
+        let ydata = ys.into_iter().map(|f| f.f64());
+
+        if step_data.len() != ydata.len() + 1 {
+            return Err(PltError::InvalidData(
+                "Data is not correctly sized. There should be one more step than y-value".to_owned()
+            ));
+        } else if step_data.clone().any(|step| step.is_nan()) {
+            return Err(PltError::InvalidData("step-data has NaN value".to_owned()));
+        } else if ydata.clone().any(|y| y.is_nan()) {
+            return Err(PltError::InvalidData("y-data has NaN value".to_owned()));
+        } else if self.desc.baseline.is_nan() {
+            return Err(PltError::InvalidData("baseline is NaN".to_owned()));
+        }
+
+        self.desc.pixel_perfect = true;
+
+        let data = StepFillData::new(step_data, ydata, self.desc.baseline);
+
+        self.subplot.fill_between_desc(self.desc, data);
+
+        Ok(())
+    }
+
+    /// Counts data in bins and fills the histogram as a staircase down to zero.
+    ///
+    /// Bins are either a count of equal width bins spanning the data, or explicit bin edges.
+    /// Each bin holds values from its lower edge up to its upper edge, with the last bin also holding its upper edge,
+    /// and values outside of the edges are not counted.
+    /// Automatic limits span the bin edges, and from zero to the largest count.
+    ///
+    /// ```
+    /// use plt::*;
+    ///
+    /// let data = [0.1, 0.4, 0.5, 0.8, 1.2, 1.3, 2.7];
+    ///
+    /// let mut subplot = Subplot::builder().build();
+    /// subplot.hist(data, 3).unwrap();
+    ///
+    /// // a marginal histogram, normalized to integrate to 1.0, with uneven bins
+    /// subplot.filler()
+    ///     .density(true)
+    ///     .orientation(Orientation::Horizontal)
+    ///     .hist(data, vec![0.0, 0.5, 1.0, 2.0, 3.0])
+    ///     .unwrap();
+    ///
+    /// assert!(subplot.hist(data, vec![1.0, 0.0]).is_err());
+    /// ```
+    pub fn hist<Ds, F, B>(mut self, data: Ds, bins: B) -> Result<(), PltError>
+    where
+        F: IntoF64,
+        Ds: IntoIterator<Item=F>,
+        B: Into<Bins>,
+    {
+        let data = data.into_iter().map(|f| f.f64()).collect::<Vec<_>>();
+        let density::Histogram { edges, counts } = density::histogram(&data, bins.into(), self.desc.density)?;
+
+        self.desc.pixel_perfect = true;
+
+        let data = HistogramData::new(edges, counts, self.desc.orientation);
+
+        self.subplot.fill_between_desc(self.desc, data);
+
+        Ok(())
+    }
+
+    /// Draws lines along the boundary curves of the filled region.
+    /// If `color` is `None`, the edges are the fill color at full opacity.
+    /// By default, no edge lines are drawn.
+    ///
+    /// ```
+    /// use plt::{Color, LineStyle};
+    ///
+    /// let xs = [0.0, 1.0, 2.0];
+    ///
+    /// let mut sp = plt::Subplot::builder().build();
+    /// sp.filler()
+    ///     .edge(LineStyle::Solid, None)
+    ///     .fill_between(&xs, &[0.0, 1.0, 0.0], &[1.0, 2.0, 1.0])
+    ///     .unwrap();
+    /// sp.filler()
+    ///     .edge(LineStyle::Dashed, Some(Color::BLACK))
+    ///     .fill_between(&xs, &[2.0, 3.0, 2.0], &[3.0, 4.0, 3.0])
+    ///     .unwrap();
+    /// ```
+    pub fn edge(mut self, line_style: LineStyle, color: Option<Color>) -> Self {
+        self.desc.edge = Some(Line {
+            style: line_style,
+            color_override: color,
+            ..Default::default()
+        });
+
+        self
+    }
+
+    /// Sets the y-value that [`Filler::fill_step`] fills down, or up, to.
+    /// Defaults to 0.0.
+    pub fn baseline(mut self, baseline: f64) -> Self {
+        self.desc.baseline = baseline;
+
+        self
+    }
+
+    /// Normalizes histograms drawn by [`Filler::hist`] by the count of values and the width of each bin,
+    /// so they integrate to 1.0. Defaults to `false`, drawing counts.
+    pub fn density(mut self, density: bool) -> Self {
+        self.desc.density = density;
+
+        self
+    }
+
+    /// Sets the direction histograms drawn by [`Filler::hist`] grow in.
+    /// Defaults to [`Orientation::Vertical`], with bins along the x-axis and counts along the y-axis,
+    /// while [`Orientation::Horizontal`] has bins along the y-axis, such as for a marginal histogram at the side of a plot.
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.desc.orientation = orientation;
+
+        self
+    }
+
+    /// Uses the secondary Y-Axis to reference y-data.
+    pub fn use_secondary_yaxis(mut self) -> Self {
+        self.desc.yaxis = AxisType::SecondaryY;
+
+        self
+    }
+
+    /// Labels the data for use in a legend.
+    pub fn label<S: AsRef<str>>(mut self, label: S) -> Self {
+        self.desc.label = label.as_ref().to_string();
+
+        self
+    }
+
+    /// Overrides the default fill color.
+    /// By default, line colors are determined by cycling through [`SubplotFormat::color_cycle`]
+    /// with an alpha value of [`SubplotFormat::fill_alpha`].
+    pub fn color(mut self, color: Color) -> Self {
+        self.desc.color_override = Some(color);
+
+        self
+    }
+
+    /// Sets the order the region is drawn in, relative to other data on the subplot.
+    /// See [`Plotter::zorder`]. Defaults to 0.
+    pub fn zorder(mut self, zorder: i32) -> Self {
+        self.desc.zorder = zorder;
+
+        self
+    }
+}
+
+/// Changes how a line added by [`Subplot::axhline`] or [`Subplot::axvline`] is drawn.
+pub struct ReferenceLine<'b> {
+    info: &'b mut ReferenceInfo,
+}
+impl ReferenceLine<'_> {
+    /// Overrides the color of the line.
+    /// By default, reference lines take [`SubplotFormat::line_color`].
+    pub fn color(self, color: Color) -> Self {
+        self.info.line.color_override = Some(color);
+
+        self
+    }
+
+    /// Sets the width of the line. Defaults to [`SubplotFormat::line_width`].
+    pub fn width(self, width: u32) -> Self {
+        self.info.line.width = width;
+
+        self
+    }
+
+    /// Sets the style of the line. Defaults to [`LineStyle::Solid`].
+    pub fn line_style(self, style: LineStyle) -> Self {
+        self.info.line.style = style;
+
+        self
+    }
+
+    /// Sets the order the line is drawn in, relative to other data on the subplot.
+    /// See [`Plotter::zorder`]. Defaults to 0.
+    pub fn zorder(self, zorder: i32) -> Self {
+        self.info.zorder = zorder;
+
+        self
+    }
+}
+
+/// Changes how a band added by [`Subplot::axspan`] is drawn.
+pub struct ReferenceSpan<'b> {
+    info: &'b mut ReferenceInfo,
+}
+impl ReferenceSpan<'_> {
+    /// Overrides the color of the band.
+    /// By default, bands take [`SubplotFormat::line_color`], partially transparent.
+    pub fn color(self, color: Color) -> Self {
+        self.info.line.color_override = Some(color);
+
+        self
+    }
+
+    /// Sets the order the band is drawn in, relative to other data on the subplot.
+    /// See [`Plotter::zorder`]. Defaults to 0.
+    pub fn zorder(self, zorder: i32) -> Self {
+        self.info.zorder = zorder;
+
+        self
+    }
+}
+
+/// Changes how an annotation added by [`Subplot::annotate`] is drawn.
+pub struct Annotation<'b> {
+    info: &'b mut AnnotationInfo,
+}
+impl Annotation<'_> {
+    /// Overrides the color of the text.
+    /// By default, annotations take [`SubplotFormat::text_color`].
+    pub fn color(self, color: Color) -> Self {
+        self.info.color_override = Some(color);
+
+        self
+    }
+
+    /// Overrides the font size of the text.
+    /// By default, annotations take [`SubplotFormat::font_size`].
+    pub fn font_size(self, font_size: f32) -> Self {
+        self.info.font_size = Some(font_size);
+
+        self
+    }
+
+    /// Rotates the text counterclockwise about its anchor point, in degrees.
+    pub fn rotation(self, degrees: f64) -> Self {
+        self.info.rotation = degrees;
+
+        self
+    }
+
+    /// Sets which side of the text is placed at its anchor point, before rotation.
+    /// Defaults to [`Alignment::Center`].
+    pub fn alignment(self, alignment: Alignment) -> Self {
+        self.info.alignment = alignment;
+
+        self
+    }
+
+    /// Moves the text away from its anchor point, in dots (pixels) at the default DPI,
+    /// with positive offsets to the right and up.
+    pub fn offset_pixels(self, (dx, dy): (f64, f64)) -> Self {
+        self.info.offset = (dx, dy);
 
         self
     }
 
-    /// Labels the data for use in a legend.
-    pub fn label<S: AsRef<str>>(mut self, label: S) -> Self {
-        self.desc.label = label.as_ref().to_string();
+    /// Draws an arrow from the text to a point in data coordinates of the primary axes.
+    /// The arrow starts just outside of the text, so the text is usually placed away from the point.
+    pub fn arrow_to(self, (x, y): (f64, f64)) -> Self {
+        self.info.arrow_target = Some((x, y));
 
         self
     }
 
-    /// Overrides the default fill color.
-    /// By default, line colors are determined by cycling through [`SubplotFormat::color_cycle`]
-    /// with an alpha value of 0.5.
-    pub fn color(mut self, color: Color) -> Self {
-        self.desc.color_override = Some(color);
+    /// Overrides the color of the arrow drawn by [`Annotation::arrow_to`].
+    /// By default, arrows take the color of the text.
+    pub fn arrow_color(self, color: Color) -> Self {
+        self.info.arrow_color = Some(color);
 
         self
     }
@@ -860,7 +3004,7 @@ impl<'a, 'b> Filler<'a, 'b> {
 
 /// Plotting line styles.
 #[non_exhaustive]
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub enum LineStyle {
     /// A solid line.
     Solid,
@@ -868,16 +3012,211 @@ pub enum LineStyle {
     Dashed,
     /// A dashed line with short dashes.
     ShortDashed,
+    /// A line of closely spaced dots.
+    Dotted,
+    /// A dashed line alternating between regular sized dashes and dots.
+    DashDot,
+    /// A dashed line with alternating lengths of dashes and gaps.
+    ///
+    /// Lengths are in unscaled points, the size of a dot at the default DPI,
+    /// and scale with the DPI like line widths. The pattern repeats along the line,
+    /// and an empty pattern draws a solid line.
+    /// Lengths must be finite and not negative, and not all zero,
+    /// or drawing the figure returns [`PltError::InvalidData`].
+    ///
+    /// ```
+    /// let mut sp = plt::Subplot::builder().build();
+    /// sp.plotter()
+    ///     .line(Some(plt::LineStyle::Custom(vec![12.0, 3.0, 3.0, 3.0, 3.0, 3.0])))
+    ///     .plot([0.0, 1.0], [0.0, 1.0])
+    ///     .unwrap();
+    /// sp.axhline(0.5).line_style(plt::LineStyle::Custom(vec![-1.0, 3.0]));
+    ///
+    /// let mut fig = <plt::Figure>::default();
+    /// fig.set_layout(plt::SingleLayout::new(sp)).unwrap();
+    /// assert!(fig.draw_bitmap().is_err());
+    /// ```
+    Custom(Vec<f64>),
 }
 
 /// Marker shapes.
 #[non_exhaustive]
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub enum MarkerStyle {
     /// A circular marker.
     Circle,
     /// A square marker.
     Square,
+    /// A triangular marker pointing up.
+    TriangleUp,
+    /// A triangular marker pointing down.
+    TriangleDown,
+    /// A square marker rotated onto its corner.
+    Diamond,
+    /// A plus sign marker.
+    Plus,
+    /// An x-shaped marker.
+    Cross,
+    /// A polygon marker, with vertices from -1.0 to 1.0 scaled by the marker size.
+    ///
+    /// Positive x is to the right, and positive y is up.
+    Custom(Vec<Point>),
+}
+
+/// A handle to a series plotted on a [`Subplot`], for changing the series after it is plotted.
+///
+/// Handles refer to series by their plotting order, so they no longer apply after [`Subplot::clear_data`].
+/// Series plotted without a handle, such as fills and bars, can be referred to by their index in plotting order.
+/// Handles to series that do not exist are rejected with [`PltError::InvalidSeries`].
+///
+/// ```
+/// use plt::*;
+///
+/// let mut subplot = Subplot::builder().build();
+/// let series = subplot.plot_handle([0.0, 1.0], [0.0, 1.0]).unwrap();
+/// subplot.plot([0.0, 1.0], [1.0, 0.0]).unwrap();
+/// subplot.filler().fill_between([0.0, 1.0], [0.0, 0.0], [1.0, 1.0]).unwrap();
+///
+/// subplot.set_series_color(series, Some(Color::BLACK)).unwrap();
+/// subplot.set_series_label(series, "selected").unwrap();
+/// subplot.set_series_line_style(series, LineStyle::Dashed).unwrap();
+/// subplot.set_series_visible(series, false).unwrap();
+/// assert!(!subplot.series_visible(series).unwrap());
+///
+/// // the fill is the third series plotted
+/// subplot.set_series_color(SeriesHandle::from(2), Some(Color::BLACK)).unwrap();
+/// assert!(matches!(
+///     subplot.set_series_color(SeriesHandle::from(3), None),
+///     Err(PltError::InvalidSeries { index: 3, nseries: 3 }),
+/// ));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SeriesHandle(usize);
+impl SeriesHandle {
+    /// The index of the series, in the order series were plotted, including fills and bars.
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+impl From<usize> for SeriesHandle {
+    /// Refers to the series at an index, in the order series were plotted, including fills and bars.
+    fn from(index: usize) -> Self {
+        Self(index)
+    }
+}
+
+/// Where a legend is drawn on a subplot.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Default)]
+pub enum LegendPosition {
+    /// Inside the top right corner of the plot area.
+    #[default]
+    TopRight,
+    /// Inside the top left corner of the plot area.
+    TopLeft,
+    /// Inside the bottom left corner of the plot area.
+    BottomLeft,
+    /// Inside the bottom right corner of the plot area.
+    BottomRight,
+    /// Outside of the plot area, to the right of the subplot.
+    Outside,
+}
+
+/// The direction something lies along a subplot.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    /// Along the x-axis.
+    Horizontal,
+    /// Along the y-axis.
+    Vertical,
+}
+
+/// The bins a histogram counts data in.
+#[derive(Clone, Debug)]
+pub enum Bins {
+    /// A count of equal width bins spanning the range of the data.
+    Count(usize),
+    /// Explicit bin edges, in increasing order, with one more edge than bins.
+    Edges(Vec<f64>),
+}
+impl From<usize> for Bins {
+    fn from(count: usize) -> Self {
+        Self::Count(count)
+    }
+}
+impl From<Vec<f64>> for Bins {
+    fn from(edges: Vec<f64>) -> Self {
+        Self::Edges(edges)
+    }
+}
+impl From<&[f64]> for Bins {
+    fn from(edges: &[f64]) -> Self {
+        Self::Edges(edges.to_vec())
+    }
+}
+
+/// Where steps occur in a staircase with one x-value per y-value.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Default)]
+pub enum StepPosition {
+    /// Each y-value holds from its x-value until the next x-value.
+    #[default]
+    Post,
+    /// Each y-value holds from the previous x-value until its x-value.
+    Pre,
+    /// Steps occur halfway between x-values.
+    Mid,
+}
+
+/// Converts data into the points of a staircase, as drawn by [`Subplot::step`].
+///
+/// With one more x-value than y-values, the x-values are step edges, and each y-value spans
+/// from its edge to the next. The position is then ignored.
+/// With one x-value per y-value, the position sets where the steps occur.
+///
+/// The points can be used to build step shaped regions with a [`Filler`], such as filled histograms.
+///
+/// ```
+/// use plt::{to_step_points, StepPosition};
+///
+/// let points = to_step_points(&[0.0, 1.0, 2.0], &[3.0, 4.0], StepPosition::Post).unwrap();
+/// assert_eq!(points, [(0.0, 3.0), (1.0, 3.0), (1.0, 4.0), (2.0, 4.0)]);
+///
+/// let points = to_step_points(&[0.0, 2.0], &[3.0, 4.0], StepPosition::Mid).unwrap();
+/// assert_eq!(points, [(0.0, 3.0), (1.0, 3.0), (1.0, 4.0), (2.0, 4.0)]);
+/// ```
+pub fn to_step_points(
+    edges: &[f64],
+    values: &[f64],
+    position: StepPosition,
+) -> Result<Vec<(f64, f64)>, PltError> {
+    if edges.len() == values.len() + 1 {
+        Ok(staircase(edges.iter().copied(), values.iter().copied()).collect())
+    } else if edges.len() == values.len() {
+        let points = match position {
+            StepPosition::Post => staircase(edges.iter().copied(), values.iter().copied())
+                .collect(),
+            StepPosition::Pre => iter::zip(
+                edges.iter().flat_map(|&x| [x, x]),
+                values.iter().flat_map(|&y| [y, y]).skip(1),
+            ).collect(),
+            StepPosition::Mid => {
+                // steps halfway between x-values, extending to the first and last x-values
+                let mids = edges.windows(2).map(|pair| 0.5 * (pair[0] + pair[1]));
+                let edges = edges.first().into_iter().copied()
+                    .chain(mids)
+                    .chain(edges.last().copied().filter(|_| edges.len() > 1));
+
+                staircase(edges, values.iter().copied()).collect()
+            },
+        };
+
+        Ok(points)
+    } else {
+        Err(PltError::InvalidData(
+            "Data is not correctly sized. There should be one more step than y-value, or one x-value per y-value".to_owned()
+        ))
+    }
 }
 
 // private
@@ -897,6 +3236,14 @@ pub(crate) struct SubplotDescriptor<'a> {
     pub secondary_xaxis: AxisDescriptor<&'a str>,
     /// The secondary axis corresponding to y-values.
     pub secondary_yaxis: AxisDescriptor<&'a str>,
+    /// The axes that draw grid lines when both axes on a side have them.
+    pub grid_axes: Axes,
+    /// Where the legend is drawn, if it is drawn.
+    pub legend: Option<LegendPosition>,
+    /// The colormap and range of values shown in a colorbar, if one is drawn.
+    pub colorbar: Option<(Colormap, (f64, f64))>,
+    /// Whether the plot area is shrunk so both primary axes have the same scale.
+    pub equal_aspect: bool,
 }
 impl Default for SubplotDescriptor<'_> {
     fn default() -> Self {
@@ -914,6 +3261,12 @@ impl Default for SubplotDescriptor<'_> {
                 limits: None,
                 span: None,
                 visible: true,
+                modifier_in_label: false,
+                inverted: false,
+                tick_label_rotation: 0.0,
+                scale: AxisScale::Linear,
+                margin: AUTOSCALE_MARGIN,
+                flat_margin: AUTOSCALE_FLAT_MARGIN,
             },
             yaxis: AxisDescriptor {
                 label: "",
@@ -926,6 +3279,12 @@ impl Default for SubplotDescriptor<'_> {
                 limits: None,
                 span: None,
                 visible: true,
+                modifier_in_label: false,
+                inverted: false,
+                tick_label_rotation: 0.0,
+                scale: AxisScale::Linear,
+                margin: AUTOSCALE_MARGIN,
+                flat_margin: AUTOSCALE_FLAT_MARGIN,
             },
             secondary_xaxis: AxisDescriptor {
                 label: "",
@@ -938,6 +3297,12 @@ impl Default for SubplotDescriptor<'_> {
                 limits: None,
                 span: None,
                 visible: true,
+                modifier_in_label: false,
+                inverted: false,
+                tick_label_rotation: 0.0,
+                scale: AxisScale::Linear,
+                margin: AUTOSCALE_MARGIN,
+                flat_margin: AUTOSCALE_FLAT_MARGIN,
             },
             secondary_yaxis: AxisDescriptor {
                 label: "",
@@ -950,16 +3315,30 @@ impl Default for SubplotDescriptor<'_> {
                 limits: None,
                 span: None,
                 visible: true,
+                modifier_in_label: false,
+                inverted: false,
+                tick_label_rotation: 0.0,
+                scale: AxisScale::Linear,
+                margin: AUTOSCALE_MARGIN,
+                flat_margin: AUTOSCALE_FLAT_MARGIN,
             },
+            grid_axes: Axes::BothPrimary,
+            legend: None,
+            colorbar: None,
+            equal_aspect: false,
         }
     }
 }
 
 /// Represents different plottable dataset types.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub(crate) enum PlotType {
     Series,
     Fill,
+    Bar,
+    Stem,
+    Heatmap,
+    Reference,
 }
 
 /// Describes data and how it should be plotted.
@@ -981,6 +3360,39 @@ pub(crate) struct PlotDescriptor {
     pub yaxis: AxisType,
     /// If plot points should be rounded to the nearest dot (pixel).
     pub pixel_perfect: bool,
+    /// Random horizontal offsets applied to data points.
+    #[cfg(feature = "jitter")]
+    pub jitter: Option<Jitter>,
+    /// The width of bars, in x-data units.
+    pub bar_width: f64,
+    /// The y-value bars extend from.
+    pub bar_base: f64,
+    /// The color to fill bars with.
+    pub bar_color: Option<Color>,
+    /// The y-value each bar extends from, overriding the bar base, for bars stacked on others.
+    pub bar_bases: Option<Vec<f64>>,
+    /// The y-value that stems are drawn from.
+    pub stem_baseline: f64,
+    /// The drawing order relative to other data, with higher values drawn on top.
+    pub zorder: i32,
+    /// The horizontal error of each data point.
+    pub xerr: Option<Vec<f64>>,
+    /// The vertical error of each data point.
+    pub yerr: Option<Vec<f64>>,
+    /// The length of the caps across the ends of error bars.
+    pub errorbar_cap_size: u32,
+    /// The color of error bars.
+    pub errorbar_color: Option<Color>,
+    /// The colormap used to color markers and heatmap cells by value.
+    pub colormap: Colormap,
+    /// How values are mapped to the colormap.
+    pub normalization: Normalization,
+    /// The values mapped to the ends of the colormap, instead of the extremes of the data.
+    pub color_limits: Option<(f64, f64)>,
+    /// The fill color of each marker, overriding the marker color.
+    pub marker_colors: Option<Vec<Color>>,
+    /// Whether NaN y-values are allowed, leaving gaps in the plotted data.
+    pub allow_gaps: bool,
 }
 impl Default for PlotDescriptor {
     fn default() -> Self {
@@ -993,7 +3405,69 @@ impl Default for PlotDescriptor {
             xaxis: AxisType::X,
             yaxis: AxisType::Y,
             pixel_perfect: false,
+            #[cfg(feature = "jitter")]
+            jitter: None,
+            bar_width: BAR_WIDTH,
+            bar_base: 0.0,
+            bar_color: None,
+            bar_bases: None,
+            stem_baseline: 0.0,
+            zorder: 0,
+            xerr: None,
+            yerr: None,
+            errorbar_cap_size: 8,
+            errorbar_color: None,
+            colormap: Colormap::Viridis,
+            normalization: Normalization::Linear,
+            color_limits: None,
+            marker_colors: None,
+            allow_gaps: false,
+        }
+    }
+}
+
+/// Describes random horizontal offsets applied to data points.
+#[cfg(feature = "jitter")]
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Jitter {
+    /// The largest offset from a point, in x-data units.
+    pub amount: f64,
+    /// The seed of the random number generator, overriding the figure's seed.
+    pub seed: Option<u64>,
+}
+#[cfg(feature = "jitter")]
+impl Jitter {
+    /// Limits the amount to half the smallest spacing between distinct x-values.
+    fn within_categories(self, data: &dyn SeriesData) -> Self {
+        let mut xs = data.data().map(|(x, _)| x).collect::<Vec<_>>();
+        xs.sort_by(f64::total_cmp);
+        xs.dedup();
+
+        let half_width = xs.windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .fold(f64::INFINITY, f64::min) / 2.0;
+
+        Self { amount: self.amount.min(half_width), ..self }
+    }
+
+    /// Offsets the x-values of points, the same way each time for a given seed.
+    /// The default seed is used if no seed is set.
+    fn apply(
+        &self,
+        points: impl Iterator<Item = (f64, f64)>,
+        default_seed: u64,
+    ) -> Vec<(f64, f64)> {
+        use rand::{Rng, SeedableRng};
+
+        if self.amount <= 0.0 {
+            return points.collect();
         }
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(self.seed.unwrap_or(default_seed));
+
+        points
+            .map(|(x, y)| (x + rng.gen_range(-self.amount..self.amount), y))
+            .collect()
     }
 }
 
@@ -1008,6 +3482,18 @@ pub(crate) struct FillDescriptor {
     pub xaxis: AxisType,
     /// Which axis to use as the y-axis.
     pub yaxis: AxisType,
+    /// The y-value filled to by step fills.
+    pub baseline: f64,
+    /// Whether histograms are normalized to integrate to 1.0.
+    pub density: bool,
+    /// The direction histograms grow in.
+    pub orientation: Orientation,
+    /// If region points should be rounded to the nearest dot (pixel).
+    pub pixel_perfect: bool,
+    /// The format of lines, optionally drawn along the region boundary.
+    pub edge: Option<Line>,
+    /// The drawing order relative to other data, with higher values drawn on top.
+    pub zorder: i32,
 }
 impl Default for FillDescriptor {
     fn default() -> Self {
@@ -1016,12 +3502,18 @@ impl Default for FillDescriptor {
             color_override: None,
             xaxis: AxisType::X,
             yaxis: AxisType::Y,
+            baseline: 0.0,
+            density: false,
+            orientation: Orientation::Vertical,
+            pixel_perfect: false,
+            edge: None,
+            zorder: 0,
         }
     }
 }
 
 /// Format for lines plotted between data points.
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub(crate) struct Line {
     /// The style of line drawn.
     pub style: LineStyle,
@@ -1040,6 +3532,27 @@ impl Default for Line {
     }
 }
 
+/// Format for error bars drawn through data points.
+#[derive(Clone, Debug)]
+pub(crate) struct ErrorBars {
+    /// The horizontal error of each data point.
+    pub xerr: Option<Vec<f64>>,
+    /// The vertical error of each data point.
+    pub yerr: Option<Vec<f64>>,
+    /// The length of the caps across the ends of error bars.
+    pub cap_size: u32,
+    /// Optionally overrides the default color of error bars.
+    pub color_override: Option<Color>,
+}
+impl ErrorBars {
+    /// The horizontal and vertical error of a data point, which are 0.0 when not set.
+    pub fn errors(&self, index: usize) -> (f64, f64) {
+        let error = |errors: &Option<Vec<f64>>| errors.as_ref().map_or(0.0, |errors| errors[index]);
+
+        (error(&self.xerr), error(&self.yerr))
+    }
+}
+
 /// Format for markers drawn at data points.
 #[derive(Clone, Debug)]
 pub(crate) struct Marker {
@@ -1049,17 +3562,31 @@ pub(crate) struct Marker {
     pub size: u32,
     /// Optionally overrides the default fill color of the marker.
     pub color_override: Option<Color>,
+    /// Whether to fill the marker, or only draw its outline.
+    pub fill: bool,
     /// Whether to draw an outline.
     pub outline: bool,
     /// Format of an optional outline.
     pub outline_format: Line,
 }
+impl Marker {
+    /// The color overriding the default color of the marker,
+    /// which is the outline color for hollow markers, as only their outlines are drawn.
+    pub fn effective_color_override(&self) -> Option<Color> {
+        if self.fill {
+            self.color_override
+        } else {
+            self.color_override.or(self.outline_format.color_override)
+        }
+    }
+}
 impl Default for Marker {
     fn default() -> Self {
         Self {
             style: MarkerStyle::Circle,
             size: 3,
             color_override: None,
+            fill: true,
             outline: false,
             outline_format: Line {
                 width: 2,
@@ -1092,6 +3619,18 @@ pub(crate) struct AxisDescriptor<S: AsRef<str>> {
     pub span: Option<(f64, f64)>,
     /// Whether to draw the axis line.
     pub visible: bool,
+    /// Whether the tick label multiplier and offset are appended to the label.
+    pub modifier_in_label: bool,
+    /// Whether the limits are reversed, so values decrease away from the origin.
+    pub inverted: bool,
+    /// The counterclockwise rotation of tick labels, in degrees.
+    pub tick_label_rotation: f64,
+    /// How values are mapped along the axis.
+    pub scale: AxisScale,
+    /// The fraction of the data range added to each end of automatic limits.
+    pub margin: f64,
+    /// The distance added to each end of automatic limits when the data has no range.
+    pub flat_margin: f64,
 }
 
 #[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
@@ -1105,10 +3644,58 @@ impl AxisType {
     pub(crate) fn iter() -> array::IntoIter<Self, 4> {
         [Self::X, Self::Y, Self::SecondaryX, Self::SecondaryY].into_iter()
     }
+
+    /// The name of the axis, for error messages.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Y => "y-axis",
+            Self::X => "x-axis",
+            Self::SecondaryY => "secondary y-axis",
+            Self::SecondaryX => "secondary x-axis",
+        }
+    }
 }
 
 pub(crate) type AxisBuf = AxisDescriptor<String>;
 impl<S: AsRef<str>> AxisDescriptor<S> {
+    /// Expands the span to cover data from `min` to `max`, and recomputes automatic limits.
+    fn fit_limits(&mut self, min: f64, max: f64) {
+        let include = match self.limit_policy {
+            Limits::Auto => None,
+            Limits::AutoInclude(value) => Some(value),
+            Limits::Manual { min: _, max: _ } => return,
+        };
+
+        // span, including any value that must be shown
+        let (min, max) = if let Some((span_min, span_max)) = self.span {
+            (f64::min(span_min, min), f64::max(span_max, max))
+        } else {
+            (min, max)
+        };
+        let (min, max) = include.map_or((min, max), |value| (min.min(value), max.max(value)));
+        self.span = Some((min, max));
+
+        // limits, padded where values are evenly spaced
+        let (min, max) = (self.scale.transform(min), self.scale.transform(max));
+        let extent = max - min;
+        let (min, max) = if extent > 0.0 {
+            (min - self.margin * extent, max + self.margin * extent)
+        } else {
+            (min - self.flat_margin, max + self.flat_margin)
+        };
+        self.limits = Some((self.scale.inverse(min), self.scale.inverse(max)));
+    }
+
+    /// Forgets the span of plotted data, and any automatic limits computed from it.
+    fn reset_limits(&mut self) {
+        if let Limits::Manual { .. } = self.limit_policy {
+            return;
+        }
+
+        self.span = None;
+        self.limits = None;
+    }
+
     fn to_buf(&self) -> AxisBuf {
         AxisBuf {
             label: self.label.as_ref().to_string(),
@@ -1121,31 +3708,207 @@ impl<S: AsRef<str>> AxisDescriptor<S> {
             limits: self.limits,
             span: self.span,
             visible: self.visible,
+            modifier_in_label: self.modifier_in_label,
+            inverted: self.inverted,
+            tick_label_rotation: self.tick_label_rotation,
+            scale: self.scale,
+            margin: self.margin,
+            flat_margin: self.flat_margin,
         }
     }
 }
 
 #[derive(Clone, Debug)]
 pub(crate) struct PlotInfo<'a> {
-    // TODO implement legend
-    #[allow(dead_code)]
     pub label: String,
     pub data: Box<dyn SeriesData + 'a>,
     pub line: Option<Line>,
     pub marker: Option<Marker>,
+    pub marker_colors: Option<Vec<Color>>,
+    pub errorbars: Option<ErrorBars>,
     pub xaxis: AxisType,
     pub yaxis: AxisType,
     pub pixel_perfect: bool,
+    #[cfg(feature = "jitter")]
+    pub jitter: Option<Jitter>,
+    pub zorder: i32,
+    pub visible: bool,
+}
+impl PlotInfo<'_> {
+    /// The data points to draw, with any jitter applied.
+    /// The seed is used for randomized offsets without a seed of their own.
+    #[cfg_attr(not(feature = "jitter"), allow(unused_variables))]
+    pub fn points(&self, seed: u64) -> Vec<(f64, f64)> {
+        #[cfg(feature = "jitter")]
+        if let Some(jitter) = &self.jitter {
+            return jitter.apply(self.data.data(), seed);
+        }
+
+        self.data.data().collect()
+    }
+
+    /// The x and y ranges covered by the data, including error bars and room for any jitter.
+    pub fn extents(&self) -> ((f64, f64), (f64, f64)) {
+        let (mut xmin, mut xmax) = (self.data.xmin(), self.data.xmax());
+        let (mut ymin, mut ymax) = (self.data.ymin(), self.data.ymax());
+        if let Some(errorbars) = &self.errorbars {
+            for (i, (x, y)) in self.data.data().enumerate() {
+                let (xerr, yerr) = errorbars.errors(i);
+                xmin = xmin.min(x - xerr);
+                xmax = xmax.max(x + xerr);
+                ymin = ymin.min(y - yerr);
+                ymax = ymax.max(y + yerr);
+            }
+        }
+
+        #[cfg(feature = "jitter")]
+        let xpad = self.jitter.map_or(0.0, |jitter| jitter.amount);
+        #[cfg(not(feature = "jitter"))]
+        let xpad = 0.0;
+
+        ((xmin - xpad, xmax + xpad), (ymin, ymax))
+    }
 }
 
 #[derive(Clone, Debug)]
 pub(crate) struct FillInfo<'a> {
-    #[allow(dead_code)]
     pub label: String,
     pub data: Box<dyn FillData + 'a>,
     pub color_override: Option<Color>,
     pub xaxis: AxisType,
     pub yaxis: AxisType,
+    pub pixel_perfect: bool,
+    pub edge: Option<Line>,
+    pub zorder: i32,
+    pub visible: bool,
+}
+impl FillInfo<'_> {
+    /// The x and y ranges covered by the filled region.
+    pub fn extents(&self) -> ((f64, f64), (f64, f64)) {
+        ((self.data.xmin(), self.data.xmax()), (self.data.ymin(), self.data.ymax()))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct BarInfo<'a> {
+    pub label: String,
+    /// The center and height of each bar.
+    pub data: Box<dyn SeriesData + 'a>,
+    pub width: f64,
+    pub base: f64,
+    /// The base of each bar, overriding the shared base, for stacked bars.
+    pub bases: Option<Vec<f64>>,
+    pub color_override: Option<Color>,
+    pub xaxis: AxisType,
+    pub yaxis: AxisType,
+    pub zorder: i32,
+    pub visible: bool,
+}
+impl BarInfo<'_> {
+    /// The lower left and upper right corners of each bar.
+    pub fn bars(&self) -> impl Iterator<Item = ((f64, f64), (f64, f64))> + '_ {
+        self.data.data().enumerate().map(|(i, (x, height))| {
+            let base = self.bases.as_ref().map_or(self.base, |bases| bases[i]);
+
+            (
+                (x - 0.5 * self.width, base.min(base + height)),
+                (x + 0.5 * self.width, base.max(base + height)),
+            )
+        })
+    }
+
+    /// The x and y ranges covered by the bars, including the base.
+    pub fn extents(&self) -> ((f64, f64), (f64, f64)) {
+        let (ymin, ymax) = self.bars()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(ymin, ymax), ((_, lower), (_, upper))| {
+                (ymin.min(lower), ymax.max(upper))
+            });
+
+        (
+            (self.data.xmin() - 0.5 * self.width, self.data.xmax() + 0.5 * self.width),
+            (ymin, ymax),
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct StemInfo<'a> {
+    pub label: String,
+    pub data: Box<dyn SeriesData + 'a>,
+    pub line: Line,
+    pub marker: Marker,
+    pub baseline: f64,
+    pub xaxis: AxisType,
+    pub yaxis: AxisType,
+    pub zorder: i32,
+    pub visible: bool,
+}
+impl StemInfo<'_> {
+    /// The x and y ranges covered by the stems, including the baseline.
+    pub fn extents(&self) -> ((f64, f64), (f64, f64)) {
+        (
+            (self.data.xmin(), self.data.xmax()),
+            (self.baseline.min(self.data.ymin()), self.baseline.max(self.data.ymax())),
+        )
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum ReferenceKind {
+    /// A line at a constant value, across the plot area.
+    Line(Orientation, f64),
+    /// A band between two constant values, across the plot area.
+    Span(Orientation, (f64, f64)),
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct ReferenceInfo {
+    pub kind: ReferenceKind,
+    /// The format of reference lines, and the color of bands.
+    pub line: Line,
+    pub zorder: i32,
+    pub visible: bool,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct AnnotationInfo {
+    pub text: String,
+    /// The anchor point, in data coordinates of the primary axes.
+    pub position: (f64, f64),
+    pub color_override: Option<Color>,
+    pub font_size: Option<f32>,
+    /// Counterclockwise rotation, in degrees.
+    pub rotation: f64,
+    pub alignment: Alignment,
+    /// Offset from the anchor point, in dots at the default DPI.
+    pub offset: (f64, f64),
+    /// The point an arrow from the text points to, in data coordinates of the primary axes.
+    pub arrow_target: Option<(f64, f64)>,
+    pub arrow_color: Option<Color>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct HeatmapInfo {
+    /// The color of each cell, with rows along y and columns along x.
+    pub colors: ndarray::Array2<Color>,
+    pub xaxis: AxisType,
+    pub yaxis: AxisType,
+    pub zorder: i32,
+    pub visible: bool,
+}
+impl HeatmapInfo {
+    /// The lower left corner of each cell, with its color.
+    /// Cells are 1.0 wide and tall.
+    pub fn cells(&self) -> impl Iterator<Item = ((f64, f64), Color)> + '_ {
+        self.colors.indexed_iter().map(|((row, col), &color)| ((col as f64, row as f64), color))
+    }
+
+    /// The x and y ranges covered by the grid.
+    pub fn extents(&self) -> ((f64, f64), (f64, f64)) {
+        let (nrows, ncols) = self.colors.dim();
+
+        ((0.0, ncols as f64), (0.0, nrows as f64))
+    }
 }
 
 pub trait IntoF64 {
@@ -1334,10 +4097,7 @@ where
     Idata: Iterator<Item=f64> + Clone,
 {
     fn data<'b>(&'b self) -> Box<dyn Iterator<Item = (f64, f64)> + 'b> {
-        Box::new(iter::zip(
-            self.edges.clone().flat_map(|x| [x, x]).skip(1),
-            self.ydata.clone().flat_map(|y| [y, y]),
-        ))
+        Box::new(staircase(self.edges.clone(), self.ydata.clone()))
     }
 
     fn xmin(&self) -> f64 {
@@ -1368,6 +4128,72 @@ where
     }
 }
 
+/// Doubles up points so each y-value spans from its edge to the next,
+/// with vertical steps between them.
+fn staircase(
+    edges: impl Iterator<Item=f64>,
+    ydata: impl Iterator<Item=f64>,
+) -> impl Iterator<Item=(f64, f64)> {
+    iter::zip(
+        edges.flat_map(|x| [x, x]).skip(1),
+        ydata.flat_map(|y| [y, y]),
+    )
+}
+
+/// Whether points have strictly increasing x-values.
+fn increasing(points: &[(f64, f64)]) -> bool {
+    points.windows(2).all(|pair| pair[0].0 < pair[1].0)
+}
+
+/// Linearly interpolates the y-value at an x-value between points with increasing x-values.
+/// Returns `None` if the x-value is outside of the points.
+fn interpolate(points: &[(f64, f64)], x: f64) -> Option<f64> {
+    let i = points.partition_point(|&(px, _)| px < x);
+    let &(x1, y1) = points.get(i)?;
+
+    if x1 == x {
+        Some(y1)
+    } else if i == 0 {
+        None
+    } else {
+        let (x0, y0) = points[i - 1];
+
+        Some(y0 + (y1 - y0) * (x - x0) / (x1 - x0))
+    }
+}
+
+/// Collects the labels and values of series with a value for each category,
+/// checking all series before any are drawn.
+fn category_series<C, L, Vs, F>(categories: &[C], series: Vec<(L, Vs)>) -> Result<Vec<(String, Vec<f64>)>, PltError>
+where
+    L: AsRef<str>,
+    F: IntoF64,
+    Vs: IntoIterator<Item=F>,
+{
+    if categories.is_empty() {
+        return Err(PltError::InvalidData("there are no categories".to_owned()));
+    } else if series.is_empty() {
+        return Err(PltError::InvalidData("there are no series".to_owned()));
+    }
+
+    let series = series.into_iter()
+        .map(|(label, values)| (label.as_ref().to_owned(), values.into_iter().map(|f| f.f64()).collect::<Vec<_>>()))
+        .collect::<Vec<_>>();
+
+    for (label, values) in series.iter() {
+        if values.len() != categories.len() {
+            return Err(PltError::InvalidData(format!(
+                "Data is not correctly sized. Series \"{}\" has {} values for {} categories",
+                label, values.len(), categories.len(),
+            )));
+        } else if values.iter().any(|value| value.is_nan()) {
+            return Err(PltError::InvalidData(format!("series \"{}\" has NaN value", label)));
+        }
+    }
+
+    Ok(series)
+}
+
 /// Holds borrowed data describing an area to be filled.
 #[derive(Copy, Clone)]
 pub(crate) struct FillBetweenData<Ix, Iy1, Iy2>
@@ -1449,6 +4275,144 @@ where
     }
 }
 
+/// Holds borrowed step data describing an area to be filled down to a baseline.
+#[derive(Copy, Clone)]
+pub(crate) struct StepFillData<Iedge, Idata>
+where
+    Iedge: Iterator<Item=f64> + Clone,
+    Idata: Iterator<Item=f64> + Clone,
+{
+    edges: Iedge,
+    ydata: Idata,
+    baseline: f64,
+}
+impl<Iedge, Idata> fmt::Debug for StepFillData<Iedge, Idata>
+where
+    Iedge: Iterator<Item=f64> + Clone,
+    Idata: Iterator<Item=f64> + Clone,
+{
+    fn fmt(&self, _: &mut Formatter) -> Result<(), fmt::Error> {
+        Ok(())
+    }
+}
+impl<Iedge, Idata> FillData for StepFillData<Iedge, Idata>
+where
+    Iedge: Iterator<Item=f64> + Clone,
+    Idata: Iterator<Item=f64> + Clone,
+{
+    fn curve1<'b>(&'b self) -> Box<dyn DoubleEndedIterator<Item = (f64, f64)> + 'b> {
+        Box::new(
+            staircase(self.edges.clone(), self.ydata.clone())
+                .collect::<Vec<_>>()
+                .into_iter()
+        )
+    }
+
+    fn curve2<'b>(&'b self) -> Box<dyn DoubleEndedIterator<Item = (f64, f64)> + 'b> {
+        // the baseline under the first and last edges closes the region
+        let first = self.edges.clone().next();
+        let last = self.edges.clone().last();
+
+        Box::new(
+            Iterator::chain(first.into_iter(), last)
+                .map(|x| (x, self.baseline))
+                .collect::<Vec<_>>()
+                .into_iter()
+        )
+    }
+
+    fn xmin(&self) -> f64 {
+        self.edges.clone().fold(f64::INFINITY, |a, b| a.min(b))
+    }
+    fn xmax(&self) -> f64 {
+        self.edges.clone().fold(f64::NEG_INFINITY, |a, b| a.max(b))
+    }
+    fn ymin(&self) -> f64 {
+        self.ydata.clone().fold(self.baseline, |a, b| a.min(b))
+    }
+    fn ymax(&self) -> f64 {
+        self.ydata.clone().fold(self.baseline, |a, b| a.max(b))
+    }
+}
+impl<Iedge, Idata> StepFillData<Iedge, Idata>
+where
+    Iedge: Iterator<Item=f64> + Clone,
+    Idata: Iterator<Item=f64> + Clone,
+{
+    /// Main constructor, taking separate array views of steps and y-values, and the baseline.
+    /// There should be one more step edge than y-values.
+    pub fn new(
+        edges: Iedge,
+        ydata: Idata,
+        baseline: f64,
+    ) -> Self {
+        Self { edges, ydata, baseline }
+    }
+}
+
+/// Holds histogram counts, describing an area filled as a staircase down, or across, to zero.
+#[derive(Clone, Debug)]
+pub(crate) struct HistogramData {
+    edges: Vec<f64>,
+    counts: Vec<f64>,
+    orientation: Orientation,
+}
+impl HistogramData {
+    /// Main constructor, taking bin edges and counts, with one more edge than counts.
+    pub fn new(edges: Vec<f64>, counts: Vec<f64>, orientation: Orientation) -> Self {
+        Self { edges, counts, orientation }
+    }
+
+    /// Places bins along the x-axis and counts along the y-axis, or the reverse for horizontal histograms.
+    fn orient(&self, (edge, count): (f64, f64)) -> (f64, f64) {
+        match self.orientation {
+            Orientation::Vertical => (edge, count),
+            Orientation::Horizontal => (count, edge),
+        }
+    }
+
+    /// The smallest and largest edges, and zero and the largest count.
+    fn extents(&self) -> ((f64, f64), (f64, f64)) {
+        let edges = (self.edges[0], self.edges[self.edges.len() - 1]);
+        let counts = (0.0, self.counts.iter().copied().fold(0.0, f64::max));
+
+        match self.orientation {
+            Orientation::Vertical => (edges, counts),
+            Orientation::Horizontal => (counts, edges),
+        }
+    }
+}
+impl FillData for HistogramData {
+    fn curve1<'b>(&'b self) -> Box<dyn DoubleEndedIterator<Item = (f64, f64)> + 'b> {
+        Box::new(
+            staircase(self.edges.iter().copied(), self.counts.iter().copied())
+                .map(|point| self.orient(point))
+                .collect::<Vec<_>>()
+                .into_iter()
+        )
+    }
+
+    fn curve2<'b>(&'b self) -> Box<dyn DoubleEndedIterator<Item = (f64, f64)> + 'b> {
+        // zero under the first and last edges closes the region
+        let (first, last) = (self.edges[0], self.edges[self.edges.len() - 1]);
+
+        Box::new([self.orient((first, 0.0)), self.orient((last, 0.0))].into_iter())
+    }
+
+    fn xmin(&self) -> f64 {
+        self.extents().0.0
+    }
+    fn xmax(&self) -> f64 {
+        self.extents().0.1
+    }
+    fn ymin(&self) -> f64 {
+        self.extents().1.0
+    }
+    fn ymax(&self) -> f64 {
+        self.extents().1.1
+    }
+}
+
 // traits
 
 /// Implemented for data that can be represented by pairs of floats to be plotted.
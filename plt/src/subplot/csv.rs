@@ -0,0 +1,111 @@
+use crate::PltError;
+
+use std::io;
+
+/// Reads two columns of numbers from CSV data, optionally skipping a header row.
+///
+/// Fields may be quoted, with `""` as an escaped quote and line breaks inside quotes.
+/// A leading byte order mark and blank lines are skipped, and input without any records
+/// returns [`PltError::InvalidData`].
+pub(super) fn read_columns<R: io::Read>(
+    reader: R,
+    x_col: usize,
+    y_col: usize,
+    header: bool,
+) -> Result<(Vec<f64>, Vec<f64>), PltError> {
+    let mut reader = ::csv::ReaderBuilder::new()
+        .has_headers(header)
+        .flexible(true)
+        .trim(::csv::Trim::All)
+        .from_reader(reader);
+
+    let mut xs = Vec::new();
+    let mut ys = Vec::new();
+
+    for record in reader.records() {
+        let record = record.map_err(convert_err)?;
+        let line = record.position().map_or(0, |position| position.line());
+
+        let cell = |col: usize| -> Result<f64, PltError> {
+            let field = record.get(col)
+                .ok_or_else(|| PltError::InvalidData(format!("line {} has no column {}", line, col)))?;
+
+            field.parse::<f64>()
+                .map_err(|_| PltError::InvalidData(format!(
+                    "line {}, column {}: `{}` is not a number",
+                    line, col, field,
+                )))
+        };
+
+        xs.push(cell(x_col)?);
+        ys.push(cell(y_col)?);
+    }
+
+    if xs.is_empty() {
+        return Err(PltError::InvalidData("CSV data has no records".to_owned()));
+    }
+
+    Ok((xs, ys))
+}
+
+/// Converts a CSV error, keeping I/O errors as [`PltError::IoError`].
+fn convert_err(err: ::csv::Error) -> PltError {
+    if err.is_io_error() {
+        match err.into_kind() {
+            ::csv::ErrorKind::Io(err) => err.into(),
+            _ => unreachable!(),
+        }
+    } else {
+        PltError::InvalidData(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(data: &str, header: bool) -> Result<(Vec<f64>, Vec<f64>), PltError> {
+        read_columns(data.as_bytes(), 0, 1, header)
+    }
+
+    #[test]
+    fn header_and_blank_lines() {
+        let (xs, ys) = read("x,y\n1,2\n\n3, 4\n", true).unwrap();
+        assert_eq!(xs, [1.0, 3.0]);
+        assert_eq!(ys, [2.0, 4.0]);
+    }
+
+    #[test]
+    fn quoted_fields_with_newlines() {
+        let (xs, ys) = read("\"x\ncoordinate\",\"y \"\"value\"\"\"\n\"1\",2\n", true).unwrap();
+        assert_eq!(xs, [1.0]);
+        assert_eq!(ys, [2.0]);
+
+        let (xs, ys) = read_columns("\"a\nb\",1,2\nc,3,4\n".as_bytes(), 1, 2, false).unwrap();
+        assert_eq!(xs, [1.0, 3.0]);
+        assert_eq!(ys, [2.0, 4.0]);
+    }
+
+    #[test]
+    fn byte_order_mark() {
+        let (xs, ys) = read("\u{feff}1,2\n3,4\n", false).unwrap();
+        assert_eq!(xs, [1.0, 3.0]);
+        assert_eq!(ys, [2.0, 4.0]);
+
+        let (xs, _) = read("\u{feff}x,y\n5,6\n", true).unwrap();
+        assert_eq!(xs, [5.0]);
+    }
+
+    #[test]
+    fn empty_input() {
+        assert!(matches!(read("", false), Err(PltError::InvalidData(_))));
+        assert!(matches!(read("\n\n", false), Err(PltError::InvalidData(_))));
+        assert!(matches!(read("x,y\n", true), Err(PltError::InvalidData(_))));
+    }
+
+    #[test]
+    fn invalid_cells() {
+        assert!(matches!(read("1,a\n", false), Err(PltError::InvalidData(_))));
+        assert!(matches!(read("1,2\n3\n", false), Err(PltError::InvalidData(_))));
+    }
+}
@@ -0,0 +1,262 @@
+use crate::PltError;
+
+use super::Bins;
+
+use std::{f64, iter};
+
+/// The number of points a density is evaluated at.
+const GRID_POINTS: usize = 200;
+/// How far the grid extends past the data, in bandwidths, so the tails reach close to zero.
+const GRID_PADDING: f64 = 3.0;
+
+/// Estimates the density of data with a Gaussian kernel, returning points along the estimate.
+///
+/// The bandwidth defaults to Silverman's rule of thumb.
+/// The estimate integrates to about 1.0 over the returned points.
+pub(super) fn gaussian_kde(
+    data: &[f64],
+    bandwidth: Option<f64>,
+) -> Result<(Vec<f64>, Vec<f64>), PltError> {
+    if data.is_empty() {
+        return Err(PltError::InvalidData("density data is empty".to_owned()));
+    } else if data.iter().any(|x| !x.is_finite()) {
+        return Err(PltError::InvalidData("density data has a NaN or infinite value".to_owned()));
+    }
+
+    let bandwidth = match bandwidth {
+        Some(bandwidth) if bandwidth > 0.0 && bandwidth.is_finite() => bandwidth,
+        Some(bandwidth) => return Err(PltError::InvalidData(format!(
+            "bandwidth of {} is not a positive number", bandwidth,
+        ))),
+        None => silverman_bandwidth(data),
+    };
+
+    let min = data.iter().copied().fold(f64::INFINITY, f64::min) - GRID_PADDING * bandwidth;
+    let max = data.iter().copied().fold(f64::NEG_INFINITY, f64::max) + GRID_PADDING * bandwidth;
+
+    let n = data.len() as f64;
+    let norm = 1.0 / (n * bandwidth * (2.0 * f64::consts::PI).sqrt());
+
+    let xs = (0..GRID_POINTS)
+        .map(|i| min + (max - min) * i as f64 / (GRID_POINTS - 1) as f64)
+        .collect::<Vec<_>>();
+    let ys = xs.iter()
+        .map(|x| {
+            norm * data.iter()
+                .map(|xi| (-0.5 * ((x - xi) / bandwidth).powi(2)).exp())
+                .sum::<f64>()
+        })
+        .collect();
+
+    Ok((xs, ys))
+}
+
+/// Counts data in bins, optionally normalized so the histogram integrates to 1.0.
+///
+/// Each bin holds values from its lower edge up to its upper edge, with the last bin also holding its upper edge.
+/// Values outside of the edges are not counted.
+/// A count of bins spans the range of the data, widened by 0.5 on each side if all values are equal.
+pub(super) fn histogram(data: &[f64], bins: Bins, density: bool) -> Result<Histogram, PltError> {
+    if data.iter().any(|x| x.is_nan()) {
+        return Err(PltError::InvalidData("histogram data has NaN value".to_owned()));
+    }
+
+    let edges = match bins {
+        Bins::Count(0) => {
+            return Err(PltError::InvalidData("histogram has no bins".to_owned()));
+        },
+        Bins::Count(nbins) => {
+            let finite = data.iter().copied().filter(|x| x.is_finite());
+            let min = finite.clone().fold(f64::INFINITY, f64::min);
+            let max = finite.fold(f64::NEG_INFINITY, f64::max);
+
+            let (min, max) = if min > max {
+                return Err(PltError::InvalidData("histogram data has no finite values".to_owned()));
+            } else if min == max {
+                (min - 0.5, max + 0.5)
+            } else {
+                (min, max)
+            };
+
+            (0..=nbins)
+                .map(|i| min + (max - min) * i as f64 / nbins as f64)
+                .collect::<Vec<_>>()
+        },
+        Bins::Edges(edges) => {
+            if edges.len() < 2 {
+                return Err(PltError::InvalidData("histogram needs at least two bin edges".to_owned()));
+            } else if edges.iter().any(|edge| !edge.is_finite()) {
+                return Err(PltError::InvalidData("histogram bin edge is not finite".to_owned()));
+            } else if edges.windows(2).any(|pair| pair[0] >= pair[1]) {
+                return Err(PltError::InvalidData("histogram bin edges are not increasing".to_owned()));
+            }
+
+            edges
+        },
+    };
+
+    let nbins = edges.len() - 1;
+    let (first, last) = (edges[0], edges[nbins]);
+    let mut counts = vec![0.0; nbins];
+    for &x in data.iter().filter(|&&x| x >= first && x <= last) {
+        // the index of the first edge above the value, so the last edge falls in the last bin
+        let bin = edges.partition_point(|&edge| edge <= x).clamp(1, nbins) - 1;
+        counts[bin] += 1.0;
+    }
+
+    if density {
+        let total = counts.iter().sum::<f64>();
+        if total > 0.0 {
+            for (count, pair) in iter::zip(counts.iter_mut(), edges.windows(2)) {
+                *count /= total * (pair[1] - pair[0]);
+            }
+        }
+    }
+
+    Ok(Histogram { edges, counts })
+}
+
+/// Bin edges and the count, or density, of data in each bin.
+pub(super) struct Histogram {
+    pub edges: Vec<f64>,
+    pub counts: Vec<f64>,
+}
+
+/// Chooses a bandwidth by Silverman's rule of thumb.
+///
+/// Samples without any spread, such as a single value, fall back to a bandwidth of 1.0.
+fn silverman_bandwidth(data: &[f64]) -> f64 {
+    let n = data.len() as f64;
+    let mean = data.iter().sum::<f64>() / n;
+    let std = if data.len() > 1 {
+        (data.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+    } else {
+        0.0
+    };
+
+    let mut sorted = data.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let iqr = quantile(&sorted, 0.75) - quantile(&sorted, 0.25);
+
+    // use the smaller measure of spread, unless it is zero
+    let spread = match (std > 0.0, iqr > 0.0) {
+        (true, true) => std.min(iqr / 1.34),
+        (true, false) => std,
+        (false, true) => iqr / 1.34,
+        (false, false) => return 1.0,
+    };
+
+    0.9 * spread * n.powf(-0.2)
+}
+
+/// Linearly interpolates a quantile, from 0.0 to 1.0, of sorted data.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let (lower, upper) = (position.floor() as usize, position.ceil() as usize);
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+/// Pairs standard normal quantiles with sorted data, for a normal quantile-quantile plot,
+/// and fits the location and scale of the data.
+///
+/// Plotting positions follow Blom's formula, and tied values share the position of their average rank,
+/// so they plot as a single point.
+/// The location and scale are the sample mean and standard deviation.
+pub(super) fn normal_qq(data: &[f64]) -> Result<QuantilePairs, PltError> {
+    if data.is_empty() {
+        return Err(PltError::InvalidData("qq data is empty".to_owned()));
+    } else if data.iter().any(|x| !x.is_finite()) {
+        return Err(PltError::InvalidData("qq data has a NaN or infinite value".to_owned()));
+    }
+
+    let mut sorted = data.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let n = sorted.len();
+    let mut theoretical = Vec::with_capacity(n);
+    let mut start = 0;
+    while start < n {
+        let end = start + sorted[start..].iter().take_while(|&&x| x == sorted[start]).count();
+
+        // average of the 1-based ranks start + 1 to end
+        let rank = (start + end + 1) as f64 / 2.0;
+        let quantile = normal_quantile((rank - 0.375) / (n as f64 + 0.25));
+        theoretical.extend(iter::repeat_n(quantile, end - start));
+
+        start = end;
+    }
+
+    let mean = sorted.iter().sum::<f64>() / n as f64;
+    let std = if n > 1 {
+        (sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64).sqrt()
+    } else {
+        0.0
+    };
+
+    Ok(QuantilePairs { theoretical, sample: sorted, location: mean, scale: std })
+}
+
+/// Quantiles of data paired with quantiles of a fitted distribution.
+pub(super) struct QuantilePairs {
+    pub theoretical: Vec<f64>,
+    pub sample: Vec<f64>,
+    pub location: f64,
+    pub scale: f64,
+}
+
+/// Computes the quantile of the standard normal distribution at a probability, from 0.0 to 1.0,
+/// by Wichura's algorithm AS 241, accurate to about 16 digits.
+// coefficients are kept exactly as published
+#[allow(clippy::excessive_precision)]
+fn normal_quantile(p: f64) -> f64 {
+    let q = p - 0.5;
+
+    if q.abs() <= 0.425 {
+        let r = 0.180625 - q * q;
+
+        return q * polynomial(r, &[
+            3.387132872796366608, 133.14166789178437745, 1971.5909503065514427,
+            13731.693765509461125, 45921.953931549871457, 67265.770927008700853,
+            33430.575583588128105, 2509.0809287301226727,
+        ]) / polynomial(r, &[
+            1.0, 42.313330701600911252, 687.1870074920579083,
+            5394.1960214247511077, 21213.794301586595867, 39307.89580009271061,
+            28729.085735721942674, 5226.495278852545925,
+        ]);
+    }
+
+    let r = (-(if q < 0.0 { p } else { 1.0 - p }).ln()).sqrt();
+    let value = if r <= 5.0 {
+        let r = r - 1.6;
+
+        polynomial(r, &[
+            1.42343711074968357734, 4.6303378461565452959, 5.7694972214606914055,
+            3.64784832476320460504, 1.27045825245236838258, 0.24178072517745061177,
+            0.0227238449892691845833, 7.7454501427834140764e-4,
+        ]) / polynomial(r, &[
+            1.0, 2.05319162663775882187, 1.6763848301838038494,
+            0.68976733498510000455, 0.14810397642748007459, 0.0151986665636164571966,
+            5.475938084995344946e-4, 1.05075007164441684324e-9,
+        ])
+    } else {
+        let r = r - 5.0;
+
+        polynomial(r, &[
+            6.6579046435011037772, 5.4637849111641143699, 1.7848265399172913358,
+            0.29656057182850489123, 0.026532189526576123093, 0.0012426609473880784386,
+            2.71155556874348757815e-5, 2.01033439929228813265e-7,
+        ]) / polynomial(r, &[
+            1.0, 0.59983220655588793769, 0.13692988092273580531,
+            0.0148753612908506148525, 7.868691311456132591e-4, 1.8463183175100546818e-5,
+            1.4215117583164458887e-7, 2.04426310338993978564e-15,
+        ])
+    };
+
+    if q < 0.0 { -value } else { value }
+}
+
+/// Evaluates a polynomial, with coefficients from the constant term up.
+fn polynomial(x: f64, coefficients: &[f64]) -> f64 {
+    coefficients.iter().rev().fold(0.0, |sum, c| sum * x + c)
+}
@@ -0,0 +1,213 @@
+use super::TimeFormat;
+
+use chrono::{DateTime, Datelike, NaiveDate, Timelike};
+
+/// The shortest tick interval, since labels show at most milliseconds.
+const MILLISECOND: f64 = 0.001;
+const MINUTE: f64 = 60.0;
+const HOUR: f64 = 60.0 * MINUTE;
+const DAY: f64 = 24.0 * HOUR;
+/// The average length of a month in the Gregorian calendar, used to estimate tick counts.
+const MONTH: f64 = 30.436875 * DAY;
+
+/// The most major ticks placed on a time axis.
+const MAX_TICKS: f64 = 6.0;
+
+/// Intervals of fixed length, in seconds, with the offset from the UNIX epoch they are aligned to.
+/// Weeks start on Mondays, and the epoch was a Thursday.
+const FIXED_INTERVALS: [(f64, f64); 19] = [
+    (1.0, 0.0), (2.0, 0.0), (5.0, 0.0), (10.0, 0.0), (15.0, 0.0), (30.0, 0.0),
+    (MINUTE, 0.0), (2.0 * MINUTE, 0.0), (5.0 * MINUTE, 0.0), (10.0 * MINUTE, 0.0),
+    (15.0 * MINUTE, 0.0), (30.0 * MINUTE, 0.0),
+    (HOUR, 0.0), (3.0 * HOUR, 0.0), (6.0 * HOUR, 0.0), (12.0 * HOUR, 0.0),
+    (DAY, 0.0), (7.0 * DAY, 4.0 * DAY), (14.0 * DAY, 4.0 * DAY),
+];
+/// Calendar intervals up to a year, in months.
+const MONTH_INTERVALS: [i64; 4] = [1, 2, 3, 6];
+
+/// A tick interval on a time axis.
+enum Interval {
+    /// A fixed length in seconds, aligned to an offset from the UNIX epoch.
+    Fixed(f64, f64),
+    /// A number of calendar months, starting in January of years divisible by the interval.
+    Months(i64),
+}
+
+/// Places major ticks on the most frequent whole calendar interval that fits within limits,
+/// where the limits are UNIX timestamps in seconds.
+///
+/// Spans of a few seconds fall back to decimal fractions of a second, down to milliseconds,
+/// and spans of decades use years in steps of 1, 2, or 5 times a power of 10.
+pub(super) fn locate_ticks(min: f64, max: f64) -> Vec<f64> {
+    match interval(max - min) {
+        Interval::Fixed(step, offset) => {
+            let first = ((min - offset) / step).ceil() as i64;
+            let last = ((max - offset) / step).floor() as i64;
+
+            (first..=last).map(|k| offset + k as f64 * step).collect()
+        },
+        Interval::Months(n) => {
+            let Some(start) = DateTime::from_timestamp(min.floor() as i64, 0) else {
+                return vec![];
+            };
+            let mut index = (start.year() as i64 * 12 + start.month0() as i64).div_euclid(n) * n;
+
+            let mut ticks = Vec::new();
+            loop {
+                let first_day = i32::try_from(index.div_euclid(12)).ok()
+                    .and_then(|year| NaiveDate::from_ymd_opt(year, index.rem_euclid(12) as u32 + 1, 1));
+                let Some(first_day) = first_day else {
+                    break;
+                };
+
+                let tick = first_day.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc().timestamp() as f64;
+                if tick > max {
+                    break;
+                } else if tick >= min {
+                    ticks.push(tick);
+                }
+                index += n;
+            }
+
+            ticks
+        },
+    }
+}
+
+/// Chooses the smallest interval giving at most [`MAX_TICKS`] ticks over a span of seconds.
+fn interval(span: f64) -> Interval {
+    let fits = |step: f64| span / step <= MAX_TICKS;
+
+    if !fits(FIXED_INTERVALS[0].0) {
+        if let Some(&(step, offset)) = FIXED_INTERVALS.iter().find(|(step, _)| fits(*step)) {
+            Interval::Fixed(step, offset)
+        } else if let Some(&n) = MONTH_INTERVALS.iter().find(|&&n| fits(n as f64 * MONTH)) {
+            Interval::Months(n)
+        } else {
+            Interval::Months(12 * nice_step(span / (12.0 * MONTH)).max(1.0) as i64)
+        }
+    } else {
+        // labels only show milliseconds, so finer steps would repeat labels
+        Interval::Fixed(nice_step(span).max(MILLISECOND), 0.0)
+    }
+}
+
+/// Finds the smallest step of 1, 2, or 5 times a power of 10 giving at most [`MAX_TICKS`] ticks over a span.
+fn nice_step(span: f64) -> f64 {
+    let power = 10f64.powf((span / MAX_TICKS).log10().floor());
+
+    [1.0, 2.0, 5.0, 10.0].into_iter()
+        .map(|factor| factor * power)
+        .find(|step| span / step <= MAX_TICKS)
+        .unwrap_or(10.0 * power)
+}
+
+/// Formats a UNIX timestamp in seconds as a UTC time.
+/// Timestamps outside of the range of dates are formatted in scientific notation.
+pub(super) fn format(timestamp: f64, format: TimeFormat) -> String {
+    // work in whole milliseconds, so times just short of a second are not truncated
+    let Some(time) = DateTime::from_timestamp_millis((timestamp * 1000.0).round() as i64) else {
+        return format!("{:e}", timestamp);
+    };
+
+    match format {
+        TimeFormat::Year => time.format("%Y").to_string(),
+        TimeFormat::YearMonth => time.format("%Y-%m").to_string(),
+        TimeFormat::Date => time.format("%Y-%m-%d").to_string(),
+        TimeFormat::MonthDay => time.format("%b %-d").to_string(),
+        TimeFormat::HourMinute => time.format("%H:%M").to_string(),
+        TimeFormat::HourMinuteSecond => {
+            let millis = time.nanosecond() / 1_000_000;
+            if millis == 0 {
+                time.format("%H:%M:%S").to_string()
+            } else {
+                let fraction = format!("{:03}", millis);
+                format!("{}.{}", time.format("%H:%M:%S"), fraction.trim_end_matches('0'))
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The UNIX timestamp of a UTC date and time.
+    fn timestamp(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> f64 {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+            .and_hms_opt(hour, minute, second).unwrap()
+            .and_utc()
+            .timestamp() as f64
+    }
+
+    fn labels(ticks: &[f64], time_format: TimeFormat) -> Vec<String> {
+        ticks.iter().map(|&tick| format(tick, time_format)).collect()
+    }
+
+    #[test]
+    fn sub_minute_spans() {
+        let start = timestamp(2024, 3, 5, 14, 30, 0);
+
+        let ticks = locate_ticks(start, start + 25.0);
+        assert_eq!(
+            labels(&ticks, TimeFormat::HourMinuteSecond),
+            ["14:30:00", "14:30:05", "14:30:10", "14:30:15", "14:30:20", "14:30:25"],
+        );
+
+        let ticks = locate_ticks(start, start + 0.5);
+        assert_eq!(
+            labels(&ticks, TimeFormat::HourMinuteSecond),
+            ["14:30:00", "14:30:00.1", "14:30:00.2", "14:30:00.3", "14:30:00.4", "14:30:00.5"],
+        );
+    }
+
+    #[test]
+    fn millisecond_spans_have_distinct_labels() {
+        let start = timestamp(2024, 3, 5, 14, 30, 0);
+
+        for span in [0.006, 0.004, 0.002, 0.0005] {
+            let ticks = locate_ticks(start, start + span);
+            let labels = labels(&ticks, TimeFormat::HourMinuteSecond);
+
+            assert!(!labels.is_empty());
+            assert!(labels.windows(2).all(|pair| pair[0] != pair[1]), "{:?}", labels);
+        }
+    }
+
+    #[test]
+    fn month_and_year_boundaries() {
+        // two months, crossing a year, ticking every other Monday
+        let ticks = locate_ticks(timestamp(2023, 11, 20, 0, 0, 0), timestamp(2024, 1, 20, 0, 0, 0));
+        assert_eq!(labels(&ticks, TimeFormat::MonthDay), ["Nov 27", "Dec 11", "Dec 25", "Jan 8"]);
+
+        // a year and a half, ticking every quarter from a leap day
+        let ticks = locate_ticks(timestamp(2024, 2, 29, 12, 0, 0), timestamp(2025, 8, 1, 0, 0, 0));
+        assert_eq!(
+            labels(&ticks, TimeFormat::YearMonth),
+            ["2024-04", "2024-07", "2024-10", "2025-01", "2025-04", "2025-07"],
+        );
+        assert!(ticks.iter().all(|&tick| format(tick, TimeFormat::Date).ends_with("-01")));
+    }
+
+    #[test]
+    fn spans_over_a_decade() {
+        let ticks = locate_ticks(timestamp(1995, 6, 1, 0, 0, 0), timestamp(2024, 6, 1, 0, 0, 0));
+        assert_eq!(labels(&ticks, TimeFormat::Year), ["2000", "2005", "2010", "2015", "2020"]);
+
+        let ticks = locate_ticks(timestamp(1500, 1, 1, 0, 0, 0), timestamp(2500, 1, 1, 0, 0, 0));
+        assert_eq!(labels(&ticks, TimeFormat::Year), ["1600", "1800", "2000", "2200", "2400"]);
+    }
+
+    #[test]
+    fn dates_before_the_epoch() {
+        assert_eq!(format(-1.0, TimeFormat::HourMinuteSecond), "23:59:59");
+        assert_eq!(format(-1.0, TimeFormat::Date), "1969-12-31");
+        assert_eq!(format(-0.25, TimeFormat::HourMinuteSecond), "23:59:59.75");
+    }
+
+    #[test]
+    fn out_of_range_timestamps() {
+        assert_eq!(format(1e300, TimeFormat::Date), "1e300");
+        assert!(locate_ticks(1e17, 2e17).is_empty());
+    }
+}
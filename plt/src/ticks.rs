@@ -0,0 +1,312 @@
+//! Numeric helpers for labeling ticks, shared by every backend.
+//!
+//! These are the same functions used to label axes, so tick labels can be
+//! computed, or checked, without drawing a figure.
+//!
+//! ```
+//! use plt::ticks::{tick_modifiers, ticks_to_labels};
+//!
+//! let ticks = [0.0, 2500.0, 5000.0, 7500.0, 10000.0];
+//! let modifiers = tick_modifiers(&ticks).unwrap();
+//!
+//! assert_eq!(modifiers.text(), "x10⁴");
+//! assert_eq!(
+//!     ticks_to_labels(&ticks, modifiers).unwrap(),
+//!     ["0.00", "0.25", "0.50", "0.75", "1.00"],
+//! );
+//! ```
+
+use crate::PltError;
+
+/// A multiplier and offset factored out of tick labels, and the precision of the remaining labels.
+///
+/// Labels are shown as `(tick - offset) / 10^multiplier`, with `precision` digits after the decimal point.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct TickModifiers {
+    /// An offset subtracted from each tick.
+    pub offset: f64,
+    /// The power of 10 each tick is divided by.
+    pub multiplier: i32,
+    /// The number of digits after the decimal point.
+    pub precision: usize,
+}
+impl TickModifiers {
+    /// Formats the multiplier and offset, as shown beside an axis.
+    /// Returns an empty string if there is neither.
+    pub fn text(&self) -> String {
+        modifier_text(self.multiplier, self.offset)
+    }
+}
+
+/// The power of 10 of the most significant digit of a number.
+/// Returns [`i32::MIN`] for zero.
+///
+/// ```
+/// use plt::ticks::sigdigit;
+///
+/// assert_eq!(sigdigit(1234.0), 3);
+/// assert_eq!(sigdigit(-5.0), 0);
+/// assert_eq!(sigdigit(0.025), -2);
+/// assert_eq!(sigdigit(0.0), i32::MIN);
+/// ```
+pub fn sigdigit(num: f64) -> i32 {
+    let mut num = num.abs();
+    if num == 0.0 {
+        return i32::MIN;
+    }
+
+    if num > 1.0 {
+        let mut ret = 0;
+        while num >= 10.0 {
+            num /= 10.0;
+            ret += 1;
+        }
+        ret
+    } else {
+        let mut ret = 0;
+        while num < 1.0 {
+            num *= 10.0;
+            ret -= 1;
+        }
+        ret
+    }
+}
+
+/// The first digits after the decimal point of a number.
+///
+/// ```
+/// use plt::ticks::decimals;
+///
+/// assert_eq!(decimals(3.25, 3), [2, 5, 0]);
+/// assert_eq!(decimals(7.0, 2), [0, 0]);
+/// ```
+pub fn decimals(mut num: f64, ndigits: u8) -> Vec<u8> {
+    let mut decimals = Vec::with_capacity(ndigits as usize);
+    for _ in 0..ndigits {
+        num -= num.floor();
+        num *= 10.0;
+        decimals.push(num.floor() as u8);
+    }
+
+    decimals
+}
+
+/// Rounds a number to a decimal place, with negative places rounding left of the decimal point.
+///
+/// ```
+/// use plt::ticks::round_to;
+///
+/// assert_eq!(round_to(3.14159, 2), 3.14);
+/// assert_eq!(round_to(1234.0, -2), 1200.0);
+/// ```
+pub fn round_to(num: f64, place: i32) -> f64 {
+    (num * f64::powi(10.0, place)).round() / f64::powi(10.0, place)
+}
+
+/// Formats an integer with superscript digits, as in the `x10ⁿ` tick label multiplier.
+///
+/// ```
+/// use plt::superscript;
+///
+/// assert_eq!(superscript(-3), "⁻³");
+/// assert_eq!(superscript(0), "⁰");
+/// assert_eq!(superscript(10), "¹⁰");
+/// assert_eq!(superscript(123), "¹²³");
+/// ```
+pub fn superscript(n: i32) -> String {
+    const DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
+    let sign = if n < 0 { Some('⁻') } else { None };
+    let digits = n.unsigned_abs().to_string();
+
+    sign.into_iter()
+        .chain(digits.chars().filter_map(|c| c.to_digit(10)).map(|digit| DIGITS[digit as usize]))
+        .collect()
+}
+
+/// Formats a tick label multiplier and offset, such as "x10³ + 5".
+/// Returns an empty string if there is neither.
+///
+/// ```
+/// use plt::ticks::modifier_text;
+///
+/// assert_eq!(modifier_text(3, 5.0), "x10³ + 5");
+/// assert_eq!(modifier_text(-2, 0.0), "x10⁻²");
+/// assert_eq!(modifier_text(0, 0.0), "");
+/// ```
+pub fn modifier_text(multiplier: i32, offset: f64) -> String {
+    if multiplier != 0 && offset != 0.0 {
+        format!("x10{} + {}", superscript(multiplier), offset)
+    } else if multiplier != 0 {
+        format!("x10{}", superscript(multiplier))
+    } else if offset != 0.0 {
+        format!("+ {}", offset)
+    } else {
+        String::new()
+    }
+}
+
+/// Finds the multiplier, offset, and precision that label a set of ticks compactly.
+///
+/// Large and small ticks are labeled with a multiplier, and ticks close together relative to their size
+/// with an offset.
+///
+/// Returns [`PltError::BadTickPlacement`] if a tick is NaN.
+///
+/// ```
+/// use plt::ticks::{tick_modifiers, TickModifiers};
+///
+/// assert_eq!(
+///     tick_modifiers(&[0.0, 0.5, 1.0]).unwrap(),
+///     TickModifiers { offset: 0.0, multiplier: 0, precision: 1 },
+/// );
+/// assert_eq!(
+///     tick_modifiers(&[1e-6, 2e-6, 3e-6]).unwrap(),
+///     TickModifiers { offset: 0.0, multiplier: -6, precision: 0 },
+/// );
+/// assert_eq!(
+///     tick_modifiers(&[100000.0, 100001.0, 100002.0]).unwrap(),
+///     TickModifiers { offset: 100000.0, multiplier: 0, precision: 0 },
+/// );
+/// assert!(tick_modifiers(&[0.0, f64::NAN]).is_err());
+/// ```
+pub fn tick_modifiers(ticks: &[f64]) -> Result<TickModifiers, PltError> {
+    // make sure there are no NaNs
+    if ticks.iter().any(|&tick| tick.is_nan()) {
+        return Err(PltError::BadTickPlacement("tick is NaN".to_owned()));
+    }
+
+    // return empty labels for empty ticks
+    if ticks.is_empty() {
+        return Ok(TickModifiers::default());
+    }
+
+    // sort ticks, as reversed axes have descending ticks
+    let mut ticks = ticks.to_vec();
+    ticks.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    // find the highest most significant digit location
+    let highest_nonzero_tick = ticks.iter()
+        .rev()
+        .cloned()
+        .find(|&n| n != 0.0);
+    let highest_nonzero_tick = if let Some(tick) = highest_nonzero_tick {
+        tick
+    } else {
+        return Ok(TickModifiers::default());
+    };
+    let mut max_multiplier = sigdigit(highest_nonzero_tick);
+
+    // get differences between ticks
+    let difs = ticks
+        .windows(2)
+        .map(|window| window[1] - window[0])
+        .collect::<Vec<_>>();
+    // find the largest difference between any two consecutive ticks, if there is more than one
+    let max_dif = *difs.iter()
+        .reduce(|max, dif| if dif > max { dif } else { max })
+        .unwrap_or(&0.0);
+    // find the highest most significant digit of the max tick difference
+    let dif_multiplier = if max_dif != 0.0 {
+        sigdigit(max_dif)
+    } else {
+        max_multiplier
+    };
+
+    // if multiplier of max dif is less than max_multiplier - 3, use offset
+    let offset = if dif_multiplier < max_multiplier - 3 {
+        ticks[0]
+    } else {
+        0.0
+    };
+
+    // get true multiplier
+    max_multiplier = sigdigit(round_to(
+        highest_nonzero_tick - offset,
+        3 - dif_multiplier,
+    ));
+    let multiplier = if !(-2..=3).contains(&max_multiplier) {
+        max_multiplier
+    } else {
+        0
+    };
+
+    // get precision
+    let max_precision = if multiplier != 0 || max_multiplier < 0 {
+        3
+    } else {
+        3 - max_multiplier
+    };
+    let shifted_ticks = if multiplier != 0 {
+        ticks.iter()
+            .map(|&tick| {
+                let rounded = (tick * f64::powi(10.0, 3 - multiplier)).round();
+                rounded * f64::powi(10.0, -3)
+            })
+            .collect::<Vec<_>>()
+    } else {
+        ticks.to_vec()
+    };
+    let precision = shifted_ticks.iter()
+        .map(|&tick| {
+            decimals(tick, max_precision as u8)
+                .iter()
+                .rposition(|&digit| digit != 0)
+                .map(|prec| prec + 1)
+                .unwrap_or(0)
+        })
+        .max()
+        .unwrap();
+
+    Ok(TickModifiers { offset, multiplier, precision })
+}
+
+/// Labels ticks with their multiplier and offset factored out, in the same order as the ticks.
+///
+/// Returns [`PltError::BadTickPlacement`] if a tick is NaN.
+///
+/// ```
+/// use plt::ticks::{tick_modifiers, ticks_to_labels};
+///
+/// let ticks = [100000.0, 100001.0, 100002.0];
+/// let labels = ticks_to_labels(&ticks, tick_modifiers(&ticks).unwrap()).unwrap();
+///
+/// assert_eq!(labels, ["0", "1", "2"]);
+/// ```
+pub fn ticks_to_labels(ticks: &[f64], modifiers: TickModifiers) -> Result<Vec<String>, PltError> {
+    // make sure there are no NaNs
+    if ticks.iter().any(|&tick| tick.is_nan()) {
+        return Err(PltError::BadTickPlacement("tick is NaN".to_owned()));
+    }
+
+    // return empty labels for empty ticks
+    if ticks.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let TickModifiers { offset, multiplier, precision } = modifiers;
+
+    // keep the order of the ticks, so each label matches its tick
+    let mut ticks = ticks.to_vec();
+    for tick in ticks.iter_mut() {
+        *tick = round_to(*tick - offset, 4 - multiplier);
+    }
+
+    // shift numbers if necessary
+    let shifted_ticks = if multiplier != 0 {
+        ticks.iter()
+            .map(|&tick| {
+                let rounded = (tick * f64::powi(10.0, 3 - multiplier)).round();
+                rounded * f64::powi(10.0, -3)
+            })
+            .collect::<Vec<_>>()
+    } else {
+        ticks.to_vec()
+    };
+
+    let labels = shifted_ticks.iter()
+        .map(|tick| format!("{0:.1$}", tick, precision))
+        .collect::<Vec<_>>();
+
+    Ok(labels)
+}